@@ -0,0 +1,159 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    fs::{read_to_string, write},
+    io,
+    path::Path,
+};
+
+use rust::{Part, Solution};
+
+const HEADER: &str = "# Recorded answers for the real inputs: <day> <part> <answer>";
+
+/// Outcome of comparing a solution with the recorded answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: String },
+    New,
+}
+
+impl Verdict {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Pass => "pass",
+            Self::Fail { .. } => "fail",
+            Self::New => "new",
+        }
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Pass => write!(f, "pass"),
+            Self::Fail { expected } => write!(f, "FAIL, expected {expected}"),
+            Self::New => write!(f, "new"),
+        }
+    }
+}
+
+/// Known answers per day and part, stored one per line as `<day> <part>
+/// <answer>` where the answer is everything after the second space.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Answers(BTreeMap<(usize, Part), String>);
+
+impl Answers {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut answers = BTreeMap::new();
+
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut fields = line.splitn(3, ' ');
+            let day = fields.next().and_then(|d| d.parse().ok());
+            let part = fields
+                .next()
+                .and_then(|p| p.parse().ok())
+                .and_then(Part::from_number);
+            let answer = fields.next().map(str::trim).filter(|a| !a.is_empty());
+
+            let (Some(day), Some(part), Some(answer)) = (day, part, answer) else {
+                return Err(format!(
+                    "line {}: expected '<day> <part> <answer>', found '{line}'",
+                    i + 1
+                ));
+            };
+            answers.insert((day, part), answer.to_string());
+        }
+
+        Ok(Self(answers))
+    }
+
+    /// Loads the answers at `path`, treating a missing file as empty.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        match read_to_string(path) {
+            Ok(text) => Self::parse(&text).map_err(|e| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{}: {e}", path.display()),
+                )
+            }),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let mut text = format!("{HEADER}\n");
+        for ((day, part), answer) in &self.0 {
+            text.push_str(&format!("{day} {} {answer}\n", part.number()));
+        }
+        write(path, text)
+    }
+
+    pub fn check(&self, day: usize, part: Part, solution: &Solution) -> Verdict {
+        match self.0.get(&(day, part)) {
+            None => Verdict::New,
+            Some(expected) if *expected == solution.value() => Verdict::Pass,
+            Some(expected) => Verdict::Fail {
+                expected: expected.clone(),
+            },
+        }
+    }
+
+    /// Stores `solution` unless it is only a marker without an answer.
+    pub fn record(&mut self, day: usize, part: Part, solution: &Solution) {
+        if solution.is_answer() {
+            self.0.insert((day, part), solution.value());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_check() {
+        let answers = Answers::parse("# comment\n1 1 42\n1 2 ABC DEF\n").unwrap();
+
+        assert_eq!(
+            Verdict::Pass,
+            answers.check(1, Part::First, &Solution::new(42))
+        );
+        assert_eq!(
+            Verdict::Pass,
+            answers.check(1, Part::Second, &Solution::from("ABC DEF"))
+        );
+        assert_eq!(
+            Verdict::Fail {
+                expected: "42".to_string()
+            },
+            answers.check(1, Part::First, &Solution::new(41))
+        );
+        assert_eq!(
+            Verdict::New,
+            answers.check(2, Part::First, &Solution::new(1))
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed_lines() {
+        assert!(Answers::parse("1 3 42").is_err());
+        assert!(Answers::parse("1 1").is_err());
+        assert!(Answers::parse("x 1 42").is_err());
+    }
+
+    #[test]
+    fn test_record_skips_markers() {
+        let mut answers = Answers::default();
+        answers.record(12, Part::Second, &Solution::Unsolved);
+        answers.record(12, Part::First, &Solution::new(565));
+
+        assert_eq!(Answers::parse("12 1 565").unwrap(), answers);
+    }
+}
@@ -0,0 +1,452 @@
+use std::{
+    fmt::Display,
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use rust::{
+    Part,
+    inputs::{self, InputLocator},
+};
+
+use crate::pool;
+
+pub const USAGE: &str = "Usage: rust [run] [OPTIONS]
+       rust bench [OPTIONS] [--warmup <N>] [--runs <N>]
+       rust list [--day <N|A-B>] [--name <TEXT>]
+       rust watch --day <N> [OPTIONS] [--interval <MS>]
+       rust new-day <N>
+
+Commands:
+  run                   Solve the selected days and report answers and timings
+  bench                 Time parsing and every selected part repeatedly and report
+                        statistics
+  list                  Show the day, title and tags of the selected solvers
+  watch                 Solve a single day again whenever its input file changes
+  new-day               Create solutions/day_NN.rs with a solver stub and test
+                        module and register it, never overwriting a file
+
+Options:
+  -d, --day <N|A-B>     Solve a single day or an inclusive range of days
+  -n, --name <TEXT>     Only select days whose title contains TEXT or that are
+                        tagged TEXT, like graph or grid
+  -p, --part <1|2>      Solve only the given part
+  -i, --input <PATH>    Read the input of the selected day from PATH, or from
+                        standard input if PATH is -
+      --inputs <DIR>    Directory holding the puzzle inputs [default: $AOC_INPUTS,
+                        or else the inputs directory next to the crate]
+      --input-pattern <PATTERN>
+                        File name of a day's input inside the inputs directory,
+                        with {dd} as the zero-padded and {d} as the plain day;
+                        repeat to try several [default: input_{dd}, day{dd}.txt]
+  -j, --jobs <N>        Solve days and parts on N threads [default: 1]
+      --parallel        Solve on one thread per available core
+  -f, --format <FORMAT> Output format: text, json or jsonl [default: text]
+      --check           Compare answers with the recorded ones in the answers file,
+                        which only hold answers for the inputs directory
+      --record          Store the current answers in the answers file
+      --answers <PATH>  File with recorded answers [default: answers.txt next to
+                        the crate]
+      --warmup <N>      Untimed runs of each part before measuring [default: 3]
+      --runs <N>        Timed runs of each part when benchmarking [default: 10]
+      --interval <MS>   How often watch polls the input file [default: 500]
+  -h, --help            Print this help
+
+Only run takes --answers, --check, --record, --format, --jobs and --parallel,
+list only takes --day and --name, and watch doesn't take --name.";
+
+const DEFAULT_ANSWERS: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../answers.txt");
+const DEFAULT_WARMUP: usize = 3;
+const DEFAULT_RUNS: usize = 10;
+const DEFAULT_INTERVAL_MS: u64 = 500;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Command {
+    Run(RunOptions),
+    Bench(BenchOptions),
+    List(RunOptions),
+    Watch(WatchOptions),
+    NewDay(usize),
+    Help,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Text,
+    Json,
+    JsonLines,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct RunOptions {
+    pub days: Option<RangeInclusive<usize>>,
+    pub name: Option<String>,
+    pub part: Option<Part>,
+    pub input: Option<PathBuf>,
+    /// The inputs directory given on the command line, if any.
+    pub inputs_dir: Option<PathBuf>,
+    pub input_patterns: Vec<String>,
+    pub answers: PathBuf,
+    pub check: bool,
+    pub record: bool,
+    pub format: OutputFormat,
+    pub jobs: usize,
+}
+
+impl RunOptions {
+    pub fn includes_part(&self, part: Part) -> bool {
+        self.part.is_none_or(|p| p == part)
+    }
+
+    /// Whether the input is read from standard input instead of a file.
+    pub fn reads_stdin(&self) -> bool {
+        self.input.as_deref() == Some(Path::new("-"))
+    }
+
+    pub fn locator(&self) -> InputLocator {
+        let dir = self.inputs_dir.clone().unwrap_or_else(inputs::default_dir);
+        InputLocator::new(dir, &self.input_patterns)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct BenchOptions {
+    pub run: RunOptions,
+    pub warmup: usize,
+    pub runs: usize,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct WatchOptions {
+    pub run: RunOptions,
+    pub interval: Duration,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct UsageError(String);
+
+impl Display for UsageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "error: {}", self.0)
+    }
+}
+
+impl Command {
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, UsageError> {
+        let mut args = args.into_iter().peekable();
+
+        if args.next_if(|a| a == "new-day").is_some() {
+            let day = args
+                .next()
+                .ok_or_else(|| UsageError("'new-day' requires a day".to_string()))?;
+            if let Some(arg) = args.next() {
+                return Err(UsageError(format!("unexpected argument '{arg}'")));
+            }
+            return Ok(Self::NewDay(parse_day(&day)?));
+        }
+
+        let command = match args.peek().map(String::as_str) {
+            Some("bench") => "bench",
+            Some("list") => "list",
+            Some("watch") => "watch",
+            Some("run") => "run",
+            _ => "",
+        };
+        if !command.is_empty() {
+            args.next();
+        }
+        let is_bench = command == "bench";
+        let is_list = command == "list";
+        let is_watch = command == "watch";
+        // Only a plain run reports answers, so only it takes the output,
+        // thread and answer options.
+        let is_run = !is_bench && !is_list && !is_watch;
+        let mut warmup = DEFAULT_WARMUP;
+        let mut runs = DEFAULT_RUNS;
+        let mut interval = Duration::from_millis(DEFAULT_INTERVAL_MS);
+
+        let mut options = RunOptions {
+            days: None,
+            name: None,
+            part: None,
+            input: None,
+            inputs_dir: None,
+            input_patterns: Vec::new(),
+            answers: PathBuf::from(DEFAULT_ANSWERS),
+            check: false,
+            record: false,
+            format: OutputFormat::Text,
+            jobs: 1,
+        };
+
+        while let Some(arg) = args.next() {
+            let mut value = |name: &str| {
+                args.next()
+                    .ok_or_else(|| UsageError(format!("'{name}' requires a value")))
+            };
+
+            match arg.as_str() {
+                "-h" | "--help" | "help" => return Ok(Self::Help),
+                "-d" | "--day" => options.days = Some(parse_days(&value(&arg)?)?),
+                "-n" | "--name" if !is_watch => options.name = Some(value(&arg)?),
+                "-p" | "--part" if !is_list => options.part = Some(parse_part(&value(&arg)?)?),
+                "-i" | "--input" if !is_list => options.input = Some(PathBuf::from(value(&arg)?)),
+                "--inputs" if !is_list => options.inputs_dir = Some(PathBuf::from(value(&arg)?)),
+                "--input-pattern" if !is_list => options.input_patterns.push(value(&arg)?),
+                "--answers" if is_run => options.answers = PathBuf::from(value(&arg)?),
+                "-f" | "--format" if is_run => options.format = parse_format(&value(&arg)?)?,
+                "-j" | "--jobs" if is_run => options.jobs = parse_count(&value(&arg)?, 1)?,
+                "--parallel" if is_run => options.jobs = pool::default_threads(),
+                "--check" if is_run => options.check = true,
+                "--record" if is_run => options.record = true,
+                "--warmup" if is_bench => warmup = parse_count(&value(&arg)?, 0)?,
+                "--runs" if is_bench => runs = parse_count(&value(&arg)?, 1)?,
+                "--interval" if is_watch => {
+                    interval = Duration::from_millis(parse_count(&value(&arg)?, 1)? as u64);
+                }
+                _ => return Err(UsageError(format!("unexpected argument '{arg}'"))),
+            }
+        }
+
+        if options.input.is_some() && options.days.as_ref().is_none_or(|d| d.start() != d.end()) {
+            return Err(UsageError(
+                "'--input' requires '--day' to select exactly one day".to_string(),
+            ));
+        }
+
+        // Recorded answers belong to the real inputs, not to an example.
+        if options.input.is_some() && (options.check || options.record) {
+            return Err(UsageError(
+                "'--check' and '--record' cannot be combined with '--input'".to_string(),
+            ));
+        }
+
+        match command {
+            "bench" => Ok(Self::Bench(BenchOptions {
+                run: options,
+                warmup,
+                runs,
+            })),
+            "list" => Ok(Self::List(options)),
+            "watch" if options.days.as_ref().is_none_or(|d| d.start() != d.end()) => Err(
+                UsageError("'watch' requires '--day' to select exactly one day".to_string()),
+            ),
+            "watch" if options.reads_stdin() => Err(UsageError(
+                "'watch' needs an input file, not standard input".to_string(),
+            )),
+            "watch" => Ok(Self::Watch(WatchOptions {
+                run: options,
+                interval,
+            })),
+            _ => Ok(Self::Run(options)),
+        }
+    }
+}
+
+fn parse_format(value: &str) -> Result<OutputFormat, UsageError> {
+    match value {
+        "text" => Ok(OutputFormat::Text),
+        "json" => Ok(OutputFormat::Json),
+        "jsonl" => Ok(OutputFormat::JsonLines),
+        _ => Err(UsageError(format!(
+            "invalid format '{value}', expected text, json or jsonl"
+        ))),
+    }
+}
+
+fn parse_count(value: &str, min: usize) -> Result<usize, UsageError> {
+    value
+        .parse::<usize>()
+        .ok()
+        .filter(|&n| n >= min)
+        .ok_or_else(|| UsageError(format!("invalid count '{value}', expected at least {min}")))
+}
+
+fn parse_day(value: &str) -> Result<usize, UsageError> {
+    value
+        .trim()
+        .parse::<usize>()
+        .ok()
+        .filter(|&d| d > 0)
+        .ok_or_else(|| UsageError(format!("invalid day '{value}'")))
+}
+
+fn parse_days(value: &str) -> Result<RangeInclusive<usize>, UsageError> {
+    let range = match value.split_once('-') {
+        Some((from, to)) => parse_day(from)?..=parse_day(to)?,
+        None => parse_day(value)?..=parse_day(value)?,
+    };
+
+    if range.is_empty() {
+        return Err(UsageError(format!("empty day range '{value}'")));
+    }
+
+    Ok(range)
+}
+
+fn parse_part(value: &str) -> Result<Part, UsageError> {
+    value
+        .parse()
+        .ok()
+        .and_then(Part::from_number)
+        .ok_or_else(|| UsageError(format!("invalid part '{value}', expected 1 or 2")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &str) -> Result<Command, UsageError> {
+        Command::parse(args.split_whitespace().map(String::from))
+    }
+
+    #[test]
+    fn test_no_arguments_runs_everything() {
+        let Ok(Command::Run(options)) = parse("") else {
+            panic!("expected run command");
+        };
+        assert_eq!(None, options.days);
+        assert_eq!(None, options.part);
+        assert_eq!(None, options.inputs_dir);
+    }
+
+    #[test]
+    fn test_single_day_part_and_input() {
+        let Ok(Command::Run(options)) = parse("run --day 7 --part 2 --input day7.txt") else {
+            panic!("expected run command");
+        };
+        assert_eq!(Some(7..=7), options.days);
+        assert_eq!(Some(Part::Second), options.part);
+        assert_eq!(Some(PathBuf::from("day7.txt")), options.input);
+        assert!(!options.reads_stdin());
+    }
+
+    #[test]
+    fn test_input_from_stdin() {
+        let Ok(Command::Run(options)) = parse("-d 3 -i -") else {
+            panic!("expected run command");
+        };
+        assert!(options.reads_stdin());
+        assert!(parse("-i -").is_err());
+        assert!(parse("watch -d 3 -i -").is_err());
+    }
+
+    #[test]
+    fn test_day_range() {
+        let Ok(Command::Run(options)) = parse("-d 3-5") else {
+            panic!("expected run command");
+        };
+        assert_eq!(Some(3..=5), options.days);
+    }
+
+    #[test]
+    fn test_repeated_input_patterns() {
+        let Ok(Command::Run(options)) = parse("--input-pattern day{dd}.txt --input-pattern {d}")
+        else {
+            panic!("expected run command");
+        };
+        assert_eq!(vec!["day{dd}.txt", "{d}"], options.input_patterns);
+    }
+
+    #[test]
+    fn test_answer_options() {
+        let Ok(Command::Run(options)) = parse("--check --record --answers a.txt") else {
+            panic!("expected run command");
+        };
+        assert!(options.check);
+        assert!(options.record);
+        assert_eq!(PathBuf::from("a.txt"), options.answers);
+    }
+
+    #[test]
+    fn test_jobs() {
+        let Ok(Command::Run(options)) = parse("-j 4") else {
+            panic!("expected run command");
+        };
+        assert_eq!(4, options.jobs);
+        assert!(parse("--jobs 0").is_err());
+    }
+
+    #[test]
+    fn test_output_format() {
+        let Ok(Command::Run(options)) = parse("--format jsonl") else {
+            panic!("expected run command");
+        };
+        assert_eq!(OutputFormat::JsonLines, options.format);
+        assert!(parse("--format xml").is_err());
+    }
+
+    #[test]
+    fn test_list_by_name() {
+        let Ok(Command::List(options)) = parse("list --name graph") else {
+            panic!("expected list command");
+        };
+        assert_eq!(Some("graph".to_string()), options.name);
+    }
+
+    #[test]
+    fn test_watch_options() {
+        let Ok(Command::Watch(options)) = parse("watch -d 7 -p 2 --interval 100") else {
+            panic!("expected watch command");
+        };
+        assert_eq!(Some(7..=7), options.run.days);
+        assert_eq!(Duration::from_millis(100), options.interval);
+        assert!(parse("watch").is_err());
+        assert!(parse("watch -d 1-2").is_err());
+        assert!(parse("run -d 1 --interval 100").is_err());
+    }
+
+    #[test]
+    fn test_new_day() {
+        assert_eq!(Ok(Command::NewDay(13)), parse("new-day 13"));
+        assert!(parse("new-day").is_err());
+        assert!(parse("new-day 0").is_err());
+        assert!(parse("new-day 13 --day 2").is_err());
+    }
+
+    #[test]
+    fn test_bench_options() {
+        let Ok(Command::Bench(options)) = parse("bench --day 2 --runs 5 --warmup 0") else {
+            panic!("expected bench command");
+        };
+        assert_eq!(Some(2..=2), options.run.days);
+        assert_eq!(5, options.runs);
+        assert_eq!(0, options.warmup);
+    }
+
+    #[test]
+    fn test_usage_errors() {
+        assert!(parse("--day").is_err());
+        assert!(parse("--day 0").is_err());
+        assert!(parse("--day 5-3").is_err());
+        assert!(parse("--part 3").is_err());
+        assert!(parse("--input x.txt").is_err());
+        assert!(parse("--day 1-2 --input x.txt").is_err());
+        assert!(parse("--day 9 --input x.txt --check").is_err());
+        assert!(parse("--day 9 --input - --record").is_err());
+        assert!(parse("--bogus").is_err());
+        assert!(parse("--runs 5").is_err());
+        assert!(parse("bench --runs 0").is_err());
+    }
+
+    #[test]
+    fn test_run_only_options() {
+        for option in [
+            "--check",
+            "--record",
+            "--answers a.txt",
+            "--format json",
+            "-j 4",
+            "--parallel",
+        ] {
+            assert!(parse(&format!("bench {option}")).is_err(), "{option}");
+            assert!(parse(&format!("list {option}")).is_err(), "{option}");
+            assert!(parse(&format!("watch -d 7 {option}")).is_err(), "{option}");
+        }
+        assert!(parse("watch -d 7 --name grid").is_err());
+        assert!(parse("list --part 1").is_err());
+        assert!(parse("list --input x.txt").is_err());
+        assert!(parse("list --inputs dir").is_err());
+    }
+}
@@ -0,0 +1,259 @@
+use std::ops::{Index, IndexMut};
+
+use crate::{point::Point2D, solvable::SolveError};
+
+/// A rectangular grid stored row by row, indexed by `Point2D` with `x` as the
+/// column and `y` as the row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// Builds a grid from rows of equal length, reporting the 1-based line of
+    /// the first row whose length differs from the first one.
+    pub fn from_rows(rows: impl IntoIterator<Item = Vec<T>>) -> Result<Self, SolveError> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+
+        for row in rows {
+            let expected = *width.get_or_insert(row.len());
+            if row.len() != expected {
+                return Err(SolveError::at_line(
+                    height + 1,
+                    format!("expected {expected} cells, found {}", row.len()),
+                ));
+            }
+            cells.extend(row);
+            height += 1;
+        }
+
+        Ok(Self {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, p: Point2D) -> bool {
+        p.x >= 0 && p.y >= 0 && (p.x as usize) < self.width && (p.y as usize) < self.height
+    }
+
+    fn offset(&self, p: Point2D) -> Option<usize> {
+        self.contains(p)
+            .then(|| p.y as usize * self.width + p.x as usize)
+    }
+
+    pub fn get(&self, p: Point2D) -> Option<&T> {
+        self.offset(p).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, p: Point2D) -> Option<&mut T> {
+        self.offset(p).map(|i| &mut self.cells[i])
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(
+            x < self.width,
+            "column {x} is outside a grid {} wide",
+            self.width
+        );
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    /// Every position of the grid in reading order.
+    pub fn points(&self) -> impl Iterator<Item = Point2D> + use<T> {
+        let width = self.width;
+        (0..self.width * self.height)
+            .map(move |i| Point2D::new((i % width) as i32, (i / width) as i32))
+    }
+
+    /// Every cell with its position, in reading order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point2D, &T)> {
+        self.points().zip(&self.cells)
+    }
+
+    /// The up to eight cells around `p` that lie inside the grid.
+    pub fn neighbors(&self, p: Point2D) -> impl Iterator<Item = (Point2D, &T)> {
+        p.neighbors()
+            .into_iter()
+            .filter_map(|n| Some((n, self.get(n)?)))
+    }
+
+    /// The up to four orthogonal neighbors of `p` that lie inside the grid.
+    pub fn neighbors4(&self, p: Point2D) -> impl Iterator<Item = (Point2D, &T)> {
+        p.neighbors4()
+            .into_iter()
+            .filter_map(|n| Some((n, self.get(n)?)))
+    }
+
+    /// The position of the first cell equal to `value`, in reading order.
+    pub fn find(&self, value: &T) -> Option<Point2D>
+    where
+        T: PartialEq,
+    {
+        self.iter().find(|(_, cell)| *cell == value).map(|(p, _)| p)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// The grid mirrored along its main diagonal, so rows become columns.
+    pub fn transposed(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width: self.height,
+            height: self.width,
+            cells: (0..self.width)
+                .flat_map(|x| self.column(x).cloned())
+                .collect(),
+        }
+    }
+
+    /// The grid turned a quarter clockwise.
+    pub fn rotated(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width: self.height,
+            height: self.width,
+            cells: (0..self.width)
+                .flat_map(|x| {
+                    (0..self.height)
+                        .rev()
+                        .map(move |y| self.cells[y * self.width + x].clone())
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Grid<u8> {
+    /// Parses one row per line of `text`, which must all be equally long.
+    pub fn parse(text: &str) -> Result<Self, SolveError> {
+        Self::from_rows(text.lines().map(|line| line.as_bytes().to_vec()))
+    }
+
+    /// Parses one row per line of `text`, filling short lines up to the
+    /// longest one with `fill`.
+    pub fn parse_padded(text: &str, fill: u8) -> Self {
+        let width = text.lines().map(str::len).max().unwrap_or(0);
+        let rows = text.lines().map(|line| {
+            let mut row = line.as_bytes().to_vec();
+            row.resize(width, fill);
+            row
+        });
+
+        Self::from_rows(rows).expect("padded rows are equally long")
+    }
+}
+
+impl<T> Index<Point2D> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: Point2D) -> &T {
+        let Some(i) = self.offset(p) else {
+            panic!("{p:?} is outside a grid of {}x{}", self.width, self.height);
+        };
+        &self.cells[i]
+    }
+}
+
+impl<T> IndexMut<Point2D> for Grid<T> {
+    fn index_mut(&mut self, p: Point2D) -> &mut T {
+        let Some(i) = self.offset(p) else {
+            panic!("{p:?} is outside a grid of {}x{}", self.width, self.height);
+        };
+        &mut self.cells[i]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn p(x: i32, y: i32) -> Point2D {
+        Point2D { x, y }
+    }
+
+    #[test]
+    fn test_parse_and_index() {
+        let grid = Grid::parse("ab.\n.cd").unwrap();
+
+        assert_eq!((3, 2), (grid.width(), grid.height()));
+        assert_eq!(b'c', grid[p(1, 1)]);
+        assert_eq!(None, grid.get(p(3, 0)));
+        assert_eq!(None, grid.get(p(0, -1)));
+        assert_eq!(Some(p(2, 1)), grid.find(&b'd'));
+        assert_eq!(
+            vec![b'b', b'c'],
+            grid.column(1).copied().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_ragged_rows() {
+        assert_eq!(
+            Err(SolveError::at_line(2, "expected 3 cells, found 2")),
+            Grid::parse("abc\nab")
+        );
+        assert_eq!(b' ', Grid::parse_padded("abc\nab", b' ')[p(2, 1)]);
+    }
+
+    #[test]
+    fn test_neighbors_stay_inside() {
+        let grid = Grid::new(3, 3, 0u8);
+
+        assert_eq!(3, grid.neighbors(p(0, 0)).count());
+        assert_eq!(8, grid.neighbors(p(1, 1)).count());
+        assert_eq!(2, grid.neighbors4(p(2, 2)).count());
+        assert_eq!(4, grid.neighbors4(p(1, 1)).count());
+    }
+
+    #[test]
+    fn test_transpose_and_rotate() {
+        let grid = Grid::parse("ab\ncd\nef").unwrap();
+
+        assert_eq!(Grid::parse("ace\nbdf").unwrap(), grid.transposed());
+        assert_eq!(Grid::parse("eca\nfdb").unwrap(), grid.rotated());
+        assert_eq!(grid, grid.rotated().rotated().rotated().rotated());
+    }
+}
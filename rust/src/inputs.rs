@@ -0,0 +1,221 @@
+use std::{
+    borrow::Cow,
+    env,
+    fmt::Display,
+    fs::read_to_string,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+/// File name templates tried in order when no pattern is configured. `{dd}`
+/// expands to the zero-padded day and `{d}` to the plain day number.
+pub const DEFAULT_PATTERNS: [&str; 2] = ["input_{dd}", "day{dd}.txt"];
+
+/// Environment variable naming the inputs directory.
+pub const INPUTS_DIR_VAR: &str = "AOC_INPUTS";
+
+/// The inputs directory from `AOC_INPUTS`, or else the `inputs` directory
+/// next to the crate, so that it does not depend on the working directory.
+pub fn default_dir() -> PathBuf {
+    env::var_os(INPUTS_DIR_VAR)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| Path::new(env!("CARGO_MANIFEST_DIR")).join("../inputs"))
+}
+
+#[derive(Debug)]
+pub enum InputError {
+    NoDirectory { dir: PathBuf },
+    Missing { day: usize, tried: Vec<PathBuf> },
+    Unreadable { path: PathBuf, source: io::Error },
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NoDirectory { dir } => write!(
+                f,
+                "inputs directory '{}' does not exist, pass '--inputs <DIR>' or set {INPUTS_DIR_VAR}",
+                dir.display()
+            ),
+            Self::Missing { day, tried } => {
+                let tried = tried
+                    .iter()
+                    .map(|p| p.display().to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                write!(f, "no input found for day {day} (tried {tried})")
+            }
+            Self::Unreadable { path, source } => {
+                write!(f, "failed to read '{}': {source}", path.display())
+            }
+        }
+    }
+}
+
+/// Finds the input file of a day by name inside a directory.
+#[derive(Debug)]
+pub struct InputLocator {
+    dir: PathBuf,
+    patterns: Vec<String>,
+}
+
+impl InputLocator {
+    pub fn new(dir: impl Into<PathBuf>, patterns: &[String]) -> Self {
+        let patterns = if patterns.is_empty() {
+            DEFAULT_PATTERNS.iter().map(ToString::to_string).collect()
+        } else {
+            patterns.to_vec()
+        };
+
+        Self {
+            dir: dir.into(),
+            patterns,
+        }
+    }
+
+    fn candidates(&self, day: usize) -> Vec<PathBuf> {
+        self.patterns
+            .iter()
+            .map(|pattern| {
+                let name = pattern
+                    .replace("{dd}", &format!("{day:02}"))
+                    .replace("{d}", &day.to_string());
+                self.dir.join(name)
+            })
+            .collect()
+    }
+
+    pub fn resolve(&self, day: usize) -> Result<PathBuf, InputError> {
+        if !self.dir.is_dir() {
+            return Err(InputError::NoDirectory {
+                dir: self.dir.clone(),
+            });
+        }
+
+        let candidates = self.candidates(day);
+
+        candidates
+            .iter()
+            .find(|path| path.is_file())
+            .cloned()
+            .ok_or(InputError::Missing {
+                day,
+                tried: candidates,
+            })
+    }
+
+    pub fn load(&self, day: usize) -> Result<String, InputError> {
+        read_input(&self.resolve(day)?)
+    }
+}
+
+/// How the text of an input is prepared before a solver parses it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputMode {
+    /// Line endings become `\n`, trailing whitespace is removed from every
+    /// line and blank lines at the end are dropped.
+    Normalized,
+    /// Only line endings are unified and trailing ones dropped, so that spaces
+    /// keep their meaning, like the column alignment of a worksheet.
+    Raw,
+}
+
+/// Prepares `text` for parsing according to `mode`. A leading byte order mark
+/// is removed in both modes.
+pub fn normalize(text: &str, mode: InputMode) -> Cow<'_, str> {
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+
+    match mode {
+        InputMode::Raw => {
+            let text = text.trim_end_matches(['\r', '\n']);
+            if text.contains('\r') {
+                Cow::Owned(text.replace("\r\n", "\n"))
+            } else {
+                Cow::Borrowed(text)
+            }
+        }
+        InputMode::Normalized => {
+            let lines: Vec<&str> = text.lines().map(str::trim_end).collect();
+            let end = lines
+                .iter()
+                .rposition(|l| !l.is_empty())
+                .map_or(0, |i| i + 1);
+            Cow::Owned(lines[..end].join("\n"))
+        }
+    }
+}
+
+pub fn read_input(path: &Path) -> Result<String, InputError> {
+    read_to_string(path).map_err(|source| InputError::Unreadable {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+pub fn read_stdin() -> Result<String, InputError> {
+    let mut input = String::new();
+    io::stdin()
+        .read_to_string(&mut input)
+        .map_err(|source| InputError::Unreadable {
+            path: PathBuf::from("<stdin>"),
+            source,
+        })?;
+    Ok(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_candidates() {
+        let locator = InputLocator::new("inputs", &[]);
+        assert_eq!(
+            vec![
+                PathBuf::from("inputs/input_07"),
+                PathBuf::from("inputs/day07.txt")
+            ],
+            locator.candidates(7)
+        );
+    }
+
+    #[test]
+    fn test_custom_pattern() {
+        let locator = InputLocator::new("inputs", &["{d}.in".to_string()]);
+        assert_eq!(vec![PathBuf::from("inputs/12.in")], locator.candidates(12));
+    }
+
+    #[test]
+    fn test_normalize_line_endings_and_trailing_whitespace() {
+        let text = "\u{feff}1 2  \r\n3 4\r\n\r\n\n";
+        assert_eq!("1 2\n3 4", normalize(text, InputMode::Normalized));
+        assert_eq!("1 2  \n3 4", normalize(text, InputMode::Raw));
+    }
+
+    #[test]
+    fn test_raw_keeps_clean_text_borrowed() {
+        assert!(matches!(
+            normalize("12 \n 3\n", InputMode::Raw),
+            Cow::Borrowed("12 \n 3")
+        ));
+    }
+
+    #[test]
+    fn test_missing_input_lists_candidates() {
+        let locator = InputLocator::new(env!("CARGO_MANIFEST_DIR"), &[]);
+        let Err(InputError::Missing { day, tried }) = locator.resolve(3) else {
+            panic!("expected missing input");
+        };
+        assert_eq!(3, day);
+        assert_eq!(2, tried.len());
+    }
+
+    #[test]
+    fn test_missing_directory() {
+        let locator = InputLocator::new("does-not-exist", &[]);
+        assert!(matches!(
+            locator.resolve(3),
+            Err(InputError::NoDirectory { .. })
+        ));
+    }
+}
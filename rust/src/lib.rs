@@ -0,0 +1,23 @@
+//! Solutions to Advent of Code 2025, one `Solvable` per day, together with
+//! the `Registry` that finds a day's solver and the helpers to load inputs.
+//!
+//! Each day lives in `solutions::day_NN`, next to the type its input is
+//! parsed into.
+
+pub mod grid;
+pub mod inputs;
+pub mod point;
+pub mod polygon;
+pub mod polyomino;
+pub mod solutions;
+mod solvable;
+
+pub use grid::Grid;
+pub use inputs::InputMode;
+pub use point::{Direction, Point, Point2D, Point3D, Rect};
+pub use polygon::{CompressedPolygon, Polygon};
+pub use polyomino::Polyomino;
+pub use solvable::{
+    Metadata, Parsed, Part, RawSolvable, Registrations, Registry, RegistryError, Solution,
+    Solvable, SolveError,
+};
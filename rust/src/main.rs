@@ -1,42 +1,43 @@
-use std::{
-    fs::{read_dir, read_to_string},
-    io,
-    path::PathBuf,
-};
-
-use crate::solvable::Registry;
-
-mod point;
-mod solutions;
-mod solvable;
-
-fn main() -> io::Result<()> {
-    let source_dir = "../inputs";
-    let mut paths: Vec<PathBuf> = read_dir(source_dir)?
-        .filter_map(Result::ok)
-        .filter(|entry| entry.path().is_file())
-        .map(|entry| entry.path())
-        .collect();
-    paths.sort();
-
-    let inputs = paths
-        .into_iter()
-        .filter_map(|path| read_to_string(path).ok())
-        .collect::<Vec<_>>();
-
-    let registry = Registry::new();
-
-    let solutions = registry.solve(&inputs);
-
-    solutions
-        .iter()
-        .enumerate()
-        .for_each(|(i, (first, second))| {
-            println!("Day {}:", i + 1);
-            println!("Task 1 -> {first}");
-            println!("Task 2 -> {second}");
-            println!("--------------------------------------------");
-        });
+use std::{env, io, process::ExitCode};
+
+use crate::cli::{Command, USAGE};
+
+mod answers;
+mod cli;
+mod json;
+mod pool;
+mod runner;
+mod scaffold;
+mod watch;
+
+fn main() -> ExitCode {
+    let command = match Command::parse(env::args().skip(1)) {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("{e}\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+
+    match command {
+        Command::Help => {
+            println!("{USAGE}");
+            ExitCode::SUCCESS
+        }
+        Command::Run(options) => exit_code(runner::run(&options)),
+        Command::Bench(options) => exit_code(runner::bench(&options)),
+        Command::List(options) => exit_code(runner::list(&options)),
+        Command::Watch(options) => exit_code(watch::watch(&options)),
+        Command::NewDay(day) => exit_code(scaffold::new_day(day)),
+    }
+}
 
-    Ok(())
+fn exit_code(result: io::Result<()>) -> ExitCode {
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        }
+    }
 }
@@ -1,4 +1,122 @@
-use std::ops::Add;
+use std::{
+    cmp::Ordering,
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+    str::FromStr,
+};
+
+use crate::solvable::{SolveError, parse_token};
+
+/// A point with `N` integer coordinates. Parsing, distances and bounding
+/// boxes only need the coordinates, so they are shared by every dimension.
+pub trait Point<const N: usize>: Copy {
+    fn from_coords(coords: [i32; N]) -> Self;
+
+    fn coords(self) -> [i32; N];
+
+    /// Parses a point written as comma-separated coordinates, like `x,y,z`,
+    /// reporting errors at `line_no` of the input.
+    fn parse_at(line_no: usize, line: &str) -> Result<Self, SolveError> {
+        let tokens: Vec<&str> = line.split(',').collect();
+        let tokens: [&str; N] = tokens.try_into().map_err(|tokens: Vec<&str>| {
+            SolveError::at_line(
+                line_no,
+                format!("expected {N} coordinates, found {}", tokens.len()),
+            )
+        })?;
+
+        let mut coords = [0; N];
+        for (coord, token) in coords.iter_mut().zip(tokens) {
+            *coord = parse_token(line_no, line, token)?;
+        }
+        Ok(Self::from_coords(coords))
+    }
+
+    /// The square of the straight-line distance, which keeps it exact.
+    fn distance_squared(self, other: Self) -> i128 {
+        zip_coords(self, other)
+            .map(|(a, b)| {
+                let d = i128::from(a) - i128::from(b);
+                d * d
+            })
+            .sum()
+    }
+
+    /// The distance when moving only along the axes.
+    fn manhattan(self, other: Self) -> u64 {
+        zip_coords(self, other)
+            .map(|(a, b)| u64::from(a.abs_diff(b)))
+            .sum()
+    }
+
+    /// The distance when diagonal moves are allowed as well.
+    fn chebyshev(self, other: Self) -> u64 {
+        zip_coords(self, other)
+            .map(|(a, b)| u64::from(a.abs_diff(b)))
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// The smallest and the largest corner of the box containing all
+    /// `points`, or `None` if there are none.
+    fn bounding_box(points: impl IntoIterator<Item = Self>) -> Option<(Self, Self)> {
+        let mut points = points.into_iter().map(Self::coords);
+        let first = points.next()?;
+        let (min, max) = points.fold((first, first), |(mut min, mut max), p| {
+            for i in 0..N {
+                min[i] = min[i].min(p[i]);
+                max[i] = max[i].max(p[i]);
+            }
+            (min, max)
+        });
+        Some((Self::from_coords(min), Self::from_coords(max)))
+    }
+}
+
+fn zip_coords<const N: usize, P: Point<N>>(a: P, b: P) -> impl Iterator<Item = (i32, i32)> {
+    a.coords().into_iter().zip(b.coords())
+}
+
+/// One of the four orthogonal directions, with north pointing to smaller `y`
+/// so that it matches the rows of a grid read top to bottom.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    /// All directions, clockwise from north.
+    pub const ALL: [Self; 4] = [Self::North, Self::East, Self::South, Self::West];
+
+    pub fn turn_right(self) -> Self {
+        match self {
+            Self::North => Self::East,
+            Self::East => Self::South,
+            Self::South => Self::West,
+            Self::West => Self::North,
+        }
+    }
+
+    pub fn turn_left(self) -> Self {
+        self.turn_right().opposite()
+    }
+
+    pub fn opposite(self) -> Self {
+        self.turn_right().turn_right()
+    }
+
+    /// The step of length one towards this direction.
+    pub fn offset(self) -> Point2D {
+        match self {
+            Self::North => Point2D::NORTH,
+            Self::East => Point2D::EAST,
+            Self::South => Point2D::SOUTH,
+            Self::West => Point2D::WEST,
+        }
+    }
+}
 
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
 pub struct Point2D {
@@ -7,11 +125,27 @@ pub struct Point2D {
 }
 
 impl Point2D {
+    pub const ORIGIN: Self = Self { x: 0, y: 0 };
+
     const NORTH: Self = Self { x: 0, y: -1 };
     const SOUTH: Self = Self { x: 0, y: 1 };
     const EAST: Self = Self { x: 1, y: 0 };
     const WEST: Self = Self { x: -1, y: 0 };
 
+    pub const fn new(x: i32, y: i32) -> Self {
+        Self { x, y }
+    }
+
+    pub fn step(self, direction: Direction) -> Self {
+        self + direction.offset()
+    }
+
+    /// The four orthogonal neighbors, clockwise from north.
+    pub fn neighbors4(self) -> [Self; 4] {
+        Direction::ALL.map(|d| self.step(d))
+    }
+
+    /// The eight surrounding points, clockwise from north-west.
     pub fn neighbors(self) -> [Self; 8] {
         [
             self + Self::NORTH + Self::WEST,
@@ -26,6 +160,37 @@ impl Point2D {
     }
 }
 
+impl Point<2> for Point2D {
+    fn from_coords([x, y]: [i32; 2]) -> Self {
+        Self { x, y }
+    }
+
+    fn coords(self) -> [i32; 2] {
+        [self.x, self.y]
+    }
+}
+
+impl FromStr for Point2D {
+    type Err = SolveError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_at(1, s)
+    }
+}
+
+/// Points are ordered in reading order: by row first, then by column.
+impl Ord for Point2D {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.y, self.x).cmp(&(other.y, other.x))
+    }
+}
+
+impl PartialOrd for Point2D {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 impl Add for Point2D {
     type Output = Self;
 
@@ -36,3 +201,332 @@ impl Add for Point2D {
         }
     }
 }
+
+impl AddAssign for Point2D {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Point2D {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+        }
+    }
+}
+
+impl SubAssign for Point2D {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul<i32> for Point2D {
+    type Output = Self;
+
+    fn mul(self, rhs: i32) -> Self::Output {
+        Self {
+            x: self.x * rhs,
+            y: self.y * rhs,
+        }
+    }
+}
+
+impl Neg for Point2D {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            x: -self.x,
+            y: -self.y,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct Point3D {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+impl Point3D {
+    pub const fn new(x: i32, y: i32, z: i32) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl Point<3> for Point3D {
+    fn from_coords([x, y, z]: [i32; 3]) -> Self {
+        Self { x, y, z }
+    }
+
+    fn coords(self) -> [i32; 3] {
+        [self.x, self.y, self.z]
+    }
+}
+
+impl FromStr for Point3D {
+    type Err = SolveError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_at(1, s)
+    }
+}
+
+/// An axis-aligned rectangle of lattice points. Both corners are inclusive, so
+/// a rectangle always covers at least one point.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct Rect {
+    min: Point2D,
+    max: Point2D,
+}
+
+impl Rect {
+    /// The rectangle spanned by two opposite corners, in any order.
+    pub fn new(a: Point2D, b: Point2D) -> Self {
+        Self {
+            min: Point2D::new(a.x.min(b.x), a.y.min(b.y)),
+            max: Point2D::new(a.x.max(b.x), a.y.max(b.y)),
+        }
+    }
+
+    /// The smallest rectangle containing all `points`, or `None` if there are
+    /// none.
+    pub fn bounding(points: impl IntoIterator<Item = Point2D>) -> Option<Self> {
+        Point2D::bounding_box(points).map(|(min, max)| Self { min, max })
+    }
+
+    pub fn min(&self) -> Point2D {
+        self.min
+    }
+
+    pub fn max(&self) -> Point2D {
+        self.max
+    }
+
+    /// The number of columns covered.
+    pub fn width(&self) -> u64 {
+        u64::from(self.max.x.abs_diff(self.min.x)) + 1
+    }
+
+    /// The number of rows covered.
+    pub fn height(&self) -> u64 {
+        u64::from(self.max.y.abs_diff(self.min.y)) + 1
+    }
+
+    /// The number of lattice points covered, counting both corners, like
+    /// tiles of a floor.
+    pub fn area(&self) -> i128 {
+        i128::from(self.width()) * i128::from(self.height())
+    }
+
+    /// The geometric area between the corners, like a shape drawn through
+    /// their centers.
+    pub fn exclusive_area(&self) -> i128 {
+        i128::from(self.width() - 1) * i128::from(self.height() - 1)
+    }
+
+    /// The four corners, clockwise from `min`.
+    pub fn corners(&self) -> [Point2D; 4] {
+        [
+            self.min,
+            Point2D::new(self.max.x, self.min.y),
+            self.max,
+            Point2D::new(self.min.x, self.max.y),
+        ]
+    }
+
+    pub fn contains(&self, p: Point2D) -> bool {
+        (self.min.x..=self.max.x).contains(&p.x) && (self.min.y..=self.max.y).contains(&p.y)
+    }
+
+    pub fn contains_rect(&self, other: &Self) -> bool {
+        self.contains(other.min) && self.contains(other.max)
+    }
+
+    /// The points covered by both rectangles, if any.
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let min = Point2D::new(self.min.x.max(other.min.x), self.min.y.max(other.min.y));
+        let max = Point2D::new(self.max.x.min(other.max.x), self.max.y.min(other.max.y));
+
+        (min.x <= max.x && min.y <= max.y).then_some(Self { min, max })
+    }
+
+    /// The smallest rectangle containing both.
+    pub fn union(&self, other: &Self) -> Self {
+        Self {
+            min: Point2D::new(self.min.x.min(other.min.x), self.min.y.min(other.min.y)),
+            max: Point2D::new(self.max.x.max(other.max.x), self.max.y.max(other.max.y)),
+        }
+    }
+
+    /// Every covered point, in reading order.
+    pub fn points(&self) -> impl Iterator<Item = Point2D> + use<> {
+        let Self { min, max } = *self;
+        (min.y..=max.y).flat_map(move |y| (min.x..=max.x).map(move |x| Point2D::new(x, y)))
+    }
+
+    /// The points on the border, each once, clockwise from `min`.
+    pub fn boundary(&self) -> impl Iterator<Item = Point2D> + use<> {
+        let Self { min, max } = *self;
+        let top = (min.x..=max.x).map(move |x| Point2D::new(x, min.y));
+        let right = (min.y + 1..=max.y).map(move |y| Point2D::new(max.x, y));
+        let bottom = (min.x..max.x)
+            .rev()
+            .map(move |x| Point2D::new(x, max.y))
+            .filter(move |_| max.y > min.y);
+        let left = (min.y + 1..max.y)
+            .rev()
+            .map(move |y| Point2D::new(min.x, y))
+            .filter(move |_| max.x > min.x);
+
+        top.chain(right).chain(bottom).chain(left)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_directions_turn_around() {
+        for d in Direction::ALL {
+            assert_eq!(d, d.turn_left().turn_right());
+            assert_eq!(d.opposite(), d.turn_right().turn_right());
+            assert_eq!(Point2D::ORIGIN, d.offset() + d.opposite().offset());
+        }
+        assert_eq!(Direction::West, Direction::North.turn_left());
+    }
+
+    #[test]
+    fn test_arithmetic_and_distances() {
+        let a = Point2D::new(2, -3);
+        let b = Point2D::new(-1, 1);
+
+        assert_eq!(Point2D::new(3, -4), a - b);
+        assert_eq!(Point2D::new(6, -9), a * 3);
+        assert_eq!(Point2D::new(-2, 3), -a);
+        assert_eq!(7, a.manhattan(b));
+        assert_eq!(4, a.chebyshev(b));
+        assert_eq!(Point2D::new(2, -4), a.step(Direction::North));
+        assert_eq!(
+            [(2, -4), (3, -3), (2, -2), (1, -3)].map(|(x, y)| Point2D::new(x, y)),
+            a.neighbors4()
+        );
+    }
+
+    #[test]
+    fn test_reading_order() {
+        let mut points = [Point2D::new(0, 1), Point2D::new(5, 0), Point2D::new(1, 0)];
+        points.sort();
+
+        assert_eq!(
+            [Point2D::new(1, 0), Point2D::new(5, 0), Point2D::new(0, 1)],
+            points
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Ok(Point2D::new(7, -1)), "7, -1".parse());
+        assert_eq!(
+            Err(SolveError::at_line(3, "expected 2 coordinates, found 1")),
+            Point2D::parse_at(3, "7")
+        );
+        assert_eq!(
+            Err(SolveError::at(2, 3, "invalid number 'a'")),
+            Point2D::parse_at(2, "1,a")
+        );
+        assert_eq!(Ok(Point3D::new(1, 2, 3)), "1,2,3".parse());
+        assert_eq!(
+            Err(SolveError::at_line(1, "expected 3 coordinates, found 2")),
+            "1,2".parse::<Point3D>()
+        );
+    }
+
+    #[test]
+    fn test_rect_normalizes_and_measures() {
+        let rect = Rect::new(Point2D::new(11, 1), Point2D::new(2, 5));
+
+        assert_eq!(Point2D::new(2, 1), rect.min());
+        assert_eq!(Point2D::new(11, 5), rect.max());
+        assert_eq!(50, rect.area());
+        assert_eq!(36, rect.exclusive_area());
+        assert_eq!(
+            Some(rect),
+            Rect::bounding([Point2D::new(2, 5), Point2D::new(7, 3), Point2D::new(11, 1)])
+        );
+    }
+
+    #[test]
+    fn test_rect_set_operations() {
+        let a = Rect::new(Point2D::new(0, 0), Point2D::new(4, 4));
+        let b = Rect::new(Point2D::new(3, 2), Point2D::new(6, 8));
+        let c = Rect::new(Point2D::new(5, 0), Point2D::new(6, 1));
+
+        assert!(a.contains(Point2D::new(4, 0)));
+        assert!(!a.contains(Point2D::new(5, 0)));
+        assert_eq!(
+            Some(Rect::new(Point2D::new(3, 2), Point2D::new(4, 4))),
+            a.intersection(&b)
+        );
+        assert_eq!(None, a.intersection(&c));
+        assert_eq!(Rect::new(Point2D::ORIGIN, Point2D::new(6, 8)), a.union(&b));
+        assert!(a.union(&c).contains_rect(&c));
+        assert!(!a.contains_rect(&b));
+    }
+
+    #[test]
+    fn test_rect_points_and_boundary() {
+        let rect = Rect::new(Point2D::ORIGIN, Point2D::new(2, 2));
+
+        assert_eq!(9, rect.points().count());
+        assert_eq!(
+            [
+                (0, 0),
+                (1, 0),
+                (2, 0),
+                (2, 1),
+                (2, 2),
+                (1, 2),
+                (0, 2),
+                (0, 1)
+            ]
+            .map(|(x, y)| Point2D::new(x, y))
+            .to_vec(),
+            rect.boundary().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            3,
+            Rect::new(Point2D::ORIGIN, Point2D::new(0, 2))
+                .boundary()
+                .count()
+        );
+        assert_eq!(
+            1,
+            Rect::new(Point2D::ORIGIN, Point2D::ORIGIN)
+                .boundary()
+                .count()
+        );
+    }
+
+    #[test]
+    fn test_3d_distances_and_bounding_box() {
+        let a = Point3D::new(162, 817, 812);
+        let b = Point3D::new(425, 690, 689);
+
+        assert_eq!(263 * 263 + 127 * 127 + 123 * 123, a.distance_squared(b));
+        assert_eq!(263 + 127 + 123, a.manhattan(b));
+        assert_eq!(263, a.chebyshev(b));
+        assert_eq!(
+            Some((Point3D::new(162, 690, 689), Point3D::new(425, 817, 812))),
+            Point3D::bounding_box([a, b])
+        );
+        assert_eq!(None, Point2D::bounding_box([]));
+    }
+}
@@ -0,0 +1,310 @@
+use crate::{
+    grid::Grid,
+    point::{Point, Point2D, Rect},
+    solvable::SolveError,
+};
+
+/// A closed loop of lattice points where every edge is horizontal or
+/// vertical, with the last vertex connected back to the first one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polygon {
+    vertices: Vec<Point2D>,
+}
+
+impl Polygon {
+    /// Builds the polygon through `vertices`, which must have at least four
+    /// corners and only axis-aligned edges.
+    pub fn new(vertices: Vec<Point2D>) -> Result<Self, SolveError> {
+        if vertices.len() < 4 {
+            return Err(SolveError::new(format!(
+                "a polygon needs at least 4 vertices, found {}",
+                vertices.len()
+            )));
+        }
+
+        let polygon = Self { vertices };
+        if let Some((a, b)) = polygon.edges().find(|(a, b)| a.x != b.x && a.y != b.y) {
+            return Err(SolveError::new(format!(
+                "the edge from {},{} to {},{} is not horizontal or vertical",
+                a.x, a.y, b.x, b.y
+            )));
+        }
+
+        Ok(polygon)
+    }
+
+    pub fn vertices(&self) -> &[Point2D] {
+        &self.vertices
+    }
+
+    /// Every edge as a pair of consecutive vertices, including the one that
+    /// closes the loop.
+    pub fn edges(&self) -> impl Iterator<Item = (Point2D, Point2D)> + '_ {
+        self.vertices
+            .iter()
+            .zip(self.vertices.iter().cycle().skip(1))
+            .map(|(&a, &b)| (a, b))
+    }
+
+    /// The geometric area enclosed by the loop through the vertices, using
+    /// the shoelace formula.
+    pub fn area(&self) -> i128 {
+        let twice: i128 = self
+            .edges()
+            .map(|(a, b)| i128::from(a.x) * i128::from(b.y) - i128::from(b.x) * i128::from(a.y))
+            .sum();
+        twice.abs() / 2
+    }
+
+    /// The number of lattice points on the loop.
+    pub fn boundary_points(&self) -> i128 {
+        self.edges().map(|(a, b)| i128::from(a.manhattan(b))).sum()
+    }
+
+    /// The number of lattice points strictly inside the loop, from Pick's
+    /// theorem: `area = interior + boundary / 2 - 1`.
+    pub fn interior_points(&self) -> i128 {
+        self.area() - self.boundary_points() / 2 + 1
+    }
+
+    /// The number of tiles covered when every lattice point is a tile, which
+    /// counts the loop itself as well as everything it encloses.
+    pub fn tiles(&self) -> i128 {
+        self.interior_points() + self.boundary_points()
+    }
+
+    pub fn on_boundary(&self, p: Point2D) -> bool {
+        self.edges().any(|(a, b)| Rect::new(a, b).contains(p))
+    }
+
+    /// Whether `p` lies inside the loop or on it.
+    pub fn contains(&self, p: Point2D) -> bool {
+        if self.on_boundary(p) {
+            return true;
+        }
+
+        // Cast a ray to the right and count the vertical edges it crosses,
+        // treating each edge as half-open so shared vertices count once.
+        let crossings = self
+            .edges()
+            .filter(|(a, b)| a.x == b.x && a.x > p.x)
+            .filter(|(a, b)| (a.y.min(b.y)..a.y.max(b.y)).contains(&p.y))
+            .count();
+        crossings % 2 == 1
+    }
+
+    /// Precomputes which parts of the plane are inside, so rectangles can be
+    /// tested without walking through their possibly huge coordinates.
+    pub fn compress(&self) -> CompressedPolygon {
+        CompressedPolygon::new(self)
+    }
+}
+
+/// A polygon drawn on a compressed grid. Every distinct coordinate gets its
+/// own row/column and every non-empty gap between two distinct coordinates is
+/// collapsed into a single row/column, so a compressed cell is either fully
+/// inside the polygon or fully outside of it.
+#[derive(Debug, Clone)]
+pub struct CompressedPolygon {
+    /// The first coordinate of every compressed column, in increasing order.
+    xs: Vec<i64>,
+    /// The first coordinate of every compressed row, in increasing order.
+    ys: Vec<i64>,
+    /// The cell at `(c, r)` is the number of outside cells in rows `< r` and
+    /// columns `< c`.
+    outside: Grid<i32>,
+}
+
+impl CompressedPolygon {
+    fn new(polygon: &Polygon) -> Self {
+        let xs = band_starts(polygon.vertices.iter().map(|p| p.x));
+        let ys = band_starts(polygon.vertices.iter().map(|p| p.y));
+
+        let w = xs.len();
+        let h = ys.len();
+        let mut boundary = Grid::new(w, h, false);
+
+        for (a, b) in polygon.edges() {
+            let edge = Rect::new(compress(&xs, &ys, a), compress(&xs, &ys, b));
+            for p in edge.points() {
+                boundary[p] = true;
+            }
+        }
+
+        let mut is_outside = Grid::new(w, h, false);
+        let mut stack = vec![Point2D::ORIGIN];
+        is_outside[Point2D::ORIGIN] = true;
+
+        while let Some(p) = stack.pop() {
+            for n in p.neighbors4() {
+                if boundary.get(n) == Some(&false) && !is_outside[n] {
+                    is_outside[n] = true;
+                    stack.push(n);
+                }
+            }
+        }
+
+        let mut outside = Grid::new(w + 1, h + 1, 0);
+        for (p, &cell) in is_outside.iter() {
+            let q = p + Point2D::new(1, 1);
+            outside[q] = outside[q - Point2D::new(1, 0)] + outside[q - Point2D::new(0, 1)]
+                - outside[p]
+                + i32::from(cell);
+        }
+
+        Self { xs, ys, outside }
+    }
+
+    /// Whether every lattice point of `rect` lies inside the polygon or on its
+    /// boundary.
+    pub fn contains_rect(&self, rect: &Rect) -> bool {
+        let min = compress(&self.xs, &self.ys, rect.min());
+        let max = compress(&self.xs, &self.ys, rect.max()) + Point2D::new(1, 1);
+
+        let o = &self.outside;
+        o[max] - o[Point2D::new(min.x, max.y)] - o[Point2D::new(max.x, min.y)] + o[min] == 0
+    }
+}
+
+/// Splits an axis into bands: everything before the first coordinate, each
+/// coordinate on its own, the values strictly between two neighbouring
+/// coordinates if there are any, and everything after the last coordinate.
+/// The outer bands give the flood fill room to walk around the loop.
+fn band_starts(values: impl Iterator<Item = i32>) -> Vec<i64> {
+    let mut coords: Vec<i64> = values.map(i64::from).collect();
+    coords.sort_unstable();
+    coords.dedup();
+
+    let mut starts = vec![i64::MIN];
+    for (i, &v) in coords.iter().enumerate() {
+        if i > 0 && v - coords[i - 1] > 1 {
+            starts.push(coords[i - 1] + 1);
+        }
+        starts.push(v);
+    }
+    if let Some(&last) = coords.last() {
+        starts.push(last + 1);
+    }
+    starts
+}
+
+/// The cell of the compressed grid where `p` ends up, which is the band
+/// holding each of its coordinates.
+fn compress(xs: &[i64], ys: &[i64], p: Point2D) -> Point2D {
+    let index =
+        |starts: &[i64], value: i32| starts.partition_point(|&s| s <= i64::from(value)) as i32 - 1;
+    Point2D::new(index(xs, p.x), index(ys, p.y))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The loop of the Day 9 example.
+    fn example() -> Polygon {
+        let vertices = [
+            (7, 1),
+            (11, 1),
+            (11, 7),
+            (9, 7),
+            (9, 5),
+            (2, 5),
+            (2, 3),
+            (7, 3),
+        ];
+        Polygon::new(vertices.map(|(x, y)| Point2D::new(x, y)).to_vec()).unwrap()
+    }
+
+    /// A square with a notch one tile wide, so neighbouring vertex
+    /// coordinates leave no gap between them.
+    fn notched() -> Polygon {
+        let vertices = [
+            (0, 0),
+            (3, 0),
+            (3, 3),
+            (2, 3),
+            (2, 1),
+            (1, 1),
+            (1, 3),
+            (0, 3),
+        ];
+        Polygon::new(vertices.map(|(x, y)| Point2D::new(x, y)).to_vec()).unwrap()
+    }
+
+    #[test]
+    fn test_area_and_pick() {
+        let polygon = example();
+
+        assert_eq!(30, polygon.area());
+        assert_eq!(30, polygon.boundary_points());
+        assert_eq!(16, polygon.interior_points());
+        assert_eq!(46, polygon.tiles());
+    }
+
+    #[test]
+    fn test_contains() {
+        let polygon = example();
+
+        assert!(polygon.contains(Point2D::new(7, 1)));
+        assert!(polygon.contains(Point2D::new(11, 4)));
+        assert!(polygon.contains(Point2D::new(5, 4)));
+        assert!(!polygon.contains(Point2D::new(5, 2)));
+        assert!(!polygon.contains(Point2D::new(12, 4)));
+        assert!(!polygon.contains(Point2D::new(7, 6)));
+        assert_eq!(
+            polygon.tiles(),
+            Rect::new(Point2D::ORIGIN, Point2D::new(12, 8))
+                .points()
+                .filter(|&p| polygon.contains(p))
+                .count() as i128
+        );
+    }
+
+    #[test]
+    fn test_compressed_contains_rect() {
+        let compressed = example().compress();
+
+        assert!(compressed.contains_rect(&Rect::new(Point2D::new(9, 5), Point2D::new(2, 3))));
+        assert!(compressed.contains_rect(&Rect::new(Point2D::new(7, 1), Point2D::new(11, 5))));
+        assert!(!compressed.contains_rect(&Rect::new(Point2D::new(2, 5), Point2D::new(11, 1))));
+        assert!(!compressed.contains_rect(&Rect::new(Point2D::new(9, 7), Point2D::new(2, 5))));
+        assert!(compressed.contains_rect(&Rect::new(Point2D::new(3, 4), Point2D::new(10, 4))));
+        assert!(!compressed.contains_rect(&Rect::new(Point2D::new(3, 4), Point2D::new(12, 4))));
+        assert!(!compressed.contains_rect(&Rect::new(Point2D::new(-5, -5), Point2D::new(-1, 0))));
+    }
+
+    #[test]
+    fn test_compressed_matches_every_point() {
+        for polygon in [example(), notched()] {
+            let compressed = polygon.compress();
+            let bounds = Rect::bounding(polygon.vertices().iter().copied()).unwrap();
+            let area = Rect::new(
+                bounds.min() - Point2D::new(1, 1),
+                bounds.max() + Point2D::new(1, 1),
+            );
+
+            for a in area.points() {
+                for b in area.points().filter(|&b| a <= b) {
+                    let rect = Rect::new(a, b);
+                    assert_eq!(
+                        rect.points().all(|p| polygon.contains(p)),
+                        compressed.contains_rect(&rect),
+                        "{rect:?}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_rejects_diagonal_edges() {
+        let vertices = [(0, 0), (4, 0), (4, 4), (1, 3)].map(|(x, y)| Point2D::new(x, y));
+
+        assert_eq!(
+            Err(SolveError::new(
+                "the edge from 4,4 to 1,3 is not horizontal or vertical"
+            )),
+            Polygon::new(vertices.to_vec())
+        );
+    }
+}
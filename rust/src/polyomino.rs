@@ -0,0 +1,79 @@
+use crate::point::{Point2D, Rect};
+
+/// A set of cells normalized so that its bounding box starts at the origin
+/// and its cells are sorted in reading order.
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct Polyomino {
+    cells: Vec<Point2D>,
+}
+
+impl Polyomino {
+    pub fn new(cells: impl IntoIterator<Item = Point2D>) -> Self {
+        let mut cells: Vec<Point2D> = cells.into_iter().collect();
+        if let Some(bounds) = Rect::bounding(cells.iter().copied()) {
+            cells.iter_mut().for_each(|p| *p -= bounds.min());
+        }
+        cells.sort_unstable();
+        cells.dedup();
+
+        Self { cells }
+    }
+
+    /// Parses a block of rows where `filled` marks a cell of the shape.
+    pub fn parse(rows: &str, filled: u8) -> Self {
+        Self::new(rows.lines().enumerate().flat_map(|(y, row)| {
+            row.bytes()
+                .enumerate()
+                .filter(move |&(_, b)| b == filled)
+                .map(move |(x, _)| Point2D {
+                    x: i32::try_from(x).unwrap(),
+                    y: i32::try_from(y).unwrap(),
+                })
+        }))
+    }
+
+    pub fn cells(&self) -> &[Point2D] {
+        &self.cells
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// The smallest rectangle containing the shape, which starts at the
+    /// origin, or `None` for a shape without cells.
+    pub fn bounds(&self) -> Option<Rect> {
+        Rect::bounding(self.cells.iter().copied())
+    }
+
+    /// Rotates the shape a quarter turn clockwise.
+    pub fn rotated(&self) -> Self {
+        Self::new(self.cells.iter().map(|p| Point2D { x: -p.y, y: p.x }))
+    }
+
+    /// Mirrors the shape along the vertical axis.
+    pub fn reflected(&self) -> Self {
+        Self::new(self.cells.iter().map(|p| Point2D { x: -p.x, y: p.y }))
+    }
+
+    /// All distinct rotations and reflections of the shape.
+    pub fn orientations(&self) -> Vec<Self> {
+        let mut result: Vec<Self> = Vec::with_capacity(8);
+        let mut current = self.clone();
+
+        for _ in 0..4 {
+            for candidate in [current.clone(), current.reflected()] {
+                if !result.contains(&candidate) {
+                    result.push(candidate);
+                }
+            }
+            current = current.rotated();
+        }
+
+        result
+    }
+}
@@ -0,0 +1,69 @@
+use std::{
+    collections::VecDeque,
+    sync::Mutex,
+    thread::{self, available_parallelism},
+};
+
+/// Number of worker threads to use when the caller asks for "all cores".
+pub fn default_threads() -> usize {
+    available_parallelism().map_or(1, usize::from)
+}
+
+/// Runs every job on a fixed set of scoped worker threads pulling from a
+/// shared queue and returns the results in the order of `jobs`, regardless of
+/// which job finished first.
+pub fn run_ordered<T, R, F>(jobs: Vec<T>, threads: usize, f: F) -> Vec<R>
+where
+    T: Send,
+    R: Send,
+    F: Fn(T) -> R + Sync,
+{
+    let n = jobs.len();
+    let queue = Mutex::new(jobs.into_iter().enumerate().collect::<VecDeque<_>>());
+    let results = Mutex::new((0..n).map(|_| None).collect::<Vec<Option<R>>>());
+
+    thread::scope(|scope| {
+        for _ in 0..threads.clamp(1, n.max(1)) {
+            scope.spawn(|| {
+                loop {
+                    let Some((idx, job)) = queue.lock().unwrap().pop_front() else {
+                        break;
+                    };
+                    let result = f(job);
+                    results.lock().unwrap()[idx] = Some(result);
+                }
+            });
+        }
+    });
+
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|r| r.expect("every job ran to completion"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn test_results_keep_job_order() {
+        let jobs: Vec<u64> = (0..20).collect();
+        let results = run_ordered(jobs, 4, |n| {
+            thread::sleep(Duration::from_millis(20 - n));
+            n * n
+        });
+
+        assert_eq!((0..20).map(|n| n * n).collect::<Vec<_>>(), results);
+    }
+
+    #[test]
+    fn test_no_jobs() {
+        let results: Vec<u8> = run_ordered(Vec::<u8>::new(), 4, |n| n);
+        assert!(results.is_empty());
+    }
+}
@@ -0,0 +1,671 @@
+use std::{
+    io,
+    time::{Duration, Instant},
+};
+
+use rust::{
+    Parsed, Part, Registry, Solution, SolveError,
+    inputs::{InputError, InputLocator, read_input, read_stdin},
+};
+
+use crate::{
+    answers::{Answers, Verdict},
+    cli::{BenchOptions, OutputFormat, RunOptions},
+    json::Json,
+    pool,
+};
+
+pub const SEPARATOR: &str = "--------------------------------------------";
+
+#[derive(Debug)]
+pub struct PartReport {
+    pub part: Part,
+    pub result: Result<Solution, SolveError>,
+    pub elapsed: Duration,
+    pub verdict: Option<Verdict>,
+}
+
+#[derive(Debug)]
+pub struct DayReport {
+    pub day: usize,
+    pub title: &'static str,
+    pub load: Duration,
+    pub parse: Duration,
+    /// Why the input could not be parsed, in which case every part failed.
+    pub parse_error: Option<SolveError>,
+    pub parts: Vec<PartReport>,
+}
+
+impl PartReport {
+    fn status(&self) -> &'static str {
+        match &self.result {
+            Ok(Solution::Unsolved) => "unsolved",
+            Ok(Solution::NotImplemented) => "not_implemented",
+            Ok(_) => "ok",
+            Err(_) => "error",
+        }
+    }
+
+    fn to_json(&self, day: usize) -> Json {
+        let (answer, error) = match &self.result {
+            Ok(solution) => (solution.is_answer().then(|| solution.value()), None),
+            Err(e) => (None, Some(e.to_string())),
+        };
+
+        Json::Object(vec![
+            ("day", Json::from(day)),
+            ("part", Json::from(self.part.number())),
+            ("status", Json::from(self.status())),
+            ("answer", Json::from(answer)),
+            ("error", Json::from(error)),
+            (
+                "verdict",
+                Json::from(self.verdict.as_ref().map(Verdict::name)),
+            ),
+            ("elapsed_ns", nanos(self.elapsed)),
+        ])
+    }
+}
+
+impl DayReport {
+    pub fn total(&self) -> Duration {
+        self.load + self.parse + self.parts.iter().map(|p| p.elapsed).sum::<Duration>()
+    }
+
+    /// The day's status and error: a failed parse, else the first failed part.
+    fn status(&self) -> (&'static str, Option<String>) {
+        if let Some(e) = &self.parse_error {
+            return ("parse_error", Some(e.to_string()));
+        }
+
+        match self.parts.iter().find_map(|p| p.result.as_ref().err()) {
+            Some(e) => ("error", Some(e.to_string())),
+            None => ("ok", None),
+        }
+    }
+
+    fn to_json(&self) -> Json {
+        let (status, error) = self.status();
+
+        Json::Object(vec![
+            ("day", Json::from(self.day)),
+            ("title", Json::from(self.title)),
+            ("status", Json::from(status)),
+            ("error", Json::from(error)),
+            ("load_ns", nanos(self.load)),
+            ("parse_ns", nanos(self.parse)),
+            ("total_ns", nanos(self.total())),
+            (
+                "parts",
+                Json::Array(self.parts.iter().map(|p| p.to_json(self.day)).collect()),
+            ),
+        ])
+    }
+}
+
+fn nanos(d: Duration) -> Json {
+    Json::Int(d.as_nanos() as i128)
+}
+
+fn missing_input_json(day: usize, error: &InputError) -> Json {
+    Json::Object(vec![
+        ("day", Json::from(day)),
+        ("part", Json::Null),
+        ("status", Json::from("missing_input")),
+        ("answer", Json::Null),
+        ("error", Json::from(error.to_string())),
+        ("verdict", Json::Null),
+        ("elapsed_ns", Json::Null),
+    ])
+}
+
+/// Minimum, median and maximum of a set of timed runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    pub fn new(mut samples: Vec<Duration>) -> Option<Self> {
+        samples.sort_unstable();
+        let n = samples.len();
+
+        let median = match n {
+            0 => return None,
+            _ if n % 2 == 1 => samples[n / 2],
+            _ => (samples[n / 2 - 1] + samples[n / 2]) / 2,
+        };
+
+        Some(Self {
+            min: samples[0],
+            median,
+            max: samples[n - 1],
+        })
+    }
+}
+
+fn selected_days(registry: &Registry, options: &RunOptions) -> io::Result<Vec<usize>> {
+    let mut days: Vec<usize> = match &options.days {
+        Some(range) => range.clone().collect(),
+        None => registry.days().collect(),
+    };
+
+    if let Some(&day) = days.iter().find(|&&d| !registry.contains(d)) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("no solver registered for day {day}"),
+        ));
+    }
+
+    if let Some(name) = &options.name {
+        days.retain(|&day| registry.metadata(day).is_some_and(|m| m.matches(name)));
+        if days.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("no selected solver matches '{name}'"),
+            ));
+        }
+    }
+
+    Ok(days)
+}
+
+fn title(registry: &Registry, day: usize) -> &'static str {
+    registry.metadata(day).map_or("", |m| m.title)
+}
+
+fn selected_parts(options: &RunOptions) -> impl Iterator<Item = Part> + '_ {
+    Part::ALL.into_iter().filter(|&p| options.includes_part(p))
+}
+
+fn load_input(options: &RunOptions, locator: &InputLocator, day: usize) -> LoadedInput {
+    let start = Instant::now();
+    let input = match &options.input {
+        Some(_) if options.reads_stdin() => read_stdin()?,
+        Some(path) => read_input(path)?,
+        None => locator.load(day)?,
+    };
+    Ok((input, start.elapsed()))
+}
+
+/// Parses a loaded input once for all of its parts, timing the parse.
+fn parse_day<'a>(
+    registry: &'a Registry,
+    day: usize,
+    input: &str,
+) -> (Result<Parsed<'a>, SolveError>, Duration) {
+    let start = Instant::now();
+    let parsed = registry
+        .parse(day, input)
+        .expect("selected days have a registered solver");
+    (parsed, start.elapsed())
+}
+
+fn solve_part(
+    answers: Option<&Answers>,
+    day: usize,
+    part: Part,
+    parsed: &Result<Parsed, SolveError>,
+) -> PartReport {
+    let start = Instant::now();
+    let result = match parsed {
+        Ok(parsed) => parsed.solve(part),
+        Err(e) => Err(SolveError {
+            part: Some(part),
+            ..e.clone()
+        }),
+    };
+    let elapsed = start.elapsed();
+    let verdict = match (answers, &result) {
+        (Some(answers), Ok(solution)) if solution.is_answer() => {
+            Some(answers.check(day, part, solution))
+        }
+        _ => None,
+    };
+
+    PartReport {
+        part,
+        result,
+        elapsed,
+        verdict,
+    }
+}
+
+type LoadedInput = Result<(String, Duration), InputError>;
+
+enum DayOutcome {
+    Solved(DayReport),
+    Missing(usize, InputError),
+}
+
+fn solve_sequential<'a>(
+    registry: &'a Registry,
+    options: &'a RunOptions,
+    answers: Option<&'a Answers>,
+    locator: &'a InputLocator,
+    days: Vec<usize>,
+) -> impl Iterator<Item = DayOutcome> + 'a {
+    days.into_iter()
+        .map(move |day| match load_input(options, locator, day) {
+            Ok((input, load)) => {
+                let (parsed, parse) = parse_day(registry, day, &input);
+                let parts = selected_parts(options)
+                    .map(|part| solve_part(answers, day, part, &parsed))
+                    .collect();
+                DayOutcome::Solved(DayReport {
+                    day,
+                    title: title(registry, day),
+                    load,
+                    parse,
+                    parse_error: parsed.err(),
+                    parts,
+                })
+            }
+            Err(e) => DayOutcome::Missing(day, e),
+        })
+}
+
+/// Loads every input up front, parses each day as its own job on the pool,
+/// then solves each selected part of each day as its own job. Results come
+/// back in job order, so the outcomes are identical in order and shape to the
+/// sequential ones.
+fn solve_parallel(
+    registry: &Registry,
+    options: &RunOptions,
+    answers: Option<&Answers>,
+    locator: &InputLocator,
+    days: Vec<usize>,
+) -> Vec<DayOutcome> {
+    let loaded: Vec<(usize, LoadedInput)> = days
+        .into_iter()
+        .map(|day| (day, load_input(options, locator, day)))
+        .collect();
+
+    let inputs: Vec<(usize, &str)> = loaded
+        .iter()
+        .filter_map(|(day, input)| Some((*day, input.as_ref().ok()?.0.as_str())))
+        .collect();
+    let parsed = pool::run_ordered(inputs.clone(), options.jobs, |(day, input)| {
+        parse_day(registry, day, input)
+    });
+
+    let jobs: Vec<(usize, Part)> = (0..inputs.len())
+        .flat_map(|i| selected_parts(options).map(move |part| (i, part)))
+        .collect();
+    let solved = pool::run_ordered(jobs, options.jobs, |(i, part)| {
+        solve_part(answers, inputs[i].0, part, &parsed[i].0)
+    });
+
+    let part_count = selected_parts(options).count();
+    let mut parsed = parsed
+        .into_iter()
+        .map(|(parsed, parse)| (parse, parsed.err()));
+    let mut solved = solved.into_iter();
+
+    loaded
+        .into_iter()
+        .map(|(day, input)| match input {
+            Ok((_, load)) => {
+                let (parse, parse_error) = parsed.next().expect("every loaded day was parsed");
+                DayOutcome::Solved(DayReport {
+                    day,
+                    title: title(registry, day),
+                    load,
+                    parse,
+                    parse_error,
+                    parts: solved.by_ref().take(part_count).collect(),
+                })
+            }
+            Err(e) => DayOutcome::Missing(day, e),
+        })
+        .collect()
+}
+
+fn print_report(report: &DayReport) {
+    println!("Day {}: {}", report.day, report.title);
+
+    for part in &report.parts {
+        let n = part.part.number();
+        let verdict = part
+            .verdict
+            .as_ref()
+            .map(|v| format!(" ({v})"))
+            .unwrap_or_default();
+        match &part.result {
+            Ok(solution) => println!("Task {n} -> {solution} [{:.2?}]{verdict}", part.elapsed),
+            Err(e) => println!("Task {n} -> Error: {e} [{:.2?}]", part.elapsed),
+        }
+    }
+
+    println!(
+        "Load: {:.2?}, parse: {:.2?}, total: {:.2?}",
+        report.load,
+        report.parse,
+        report.total()
+    );
+    println!("{SEPARATOR}");
+}
+
+pub fn run(options: &RunOptions) -> io::Result<()> {
+    let registry = Registry::new().map_err(|e| io::Error::other(e.to_string()))?;
+    let days = selected_days(&registry, options)?;
+    let locator = options.locator();
+    let mut answers = if options.check || options.record {
+        Some(Answers::load(&options.answers)?)
+    } else {
+        None
+    };
+    let mut missing = 0;
+    let mut failed = 0;
+    let mut mismatched = 0;
+    let mut recorded = Vec::new();
+    let mut json_days = Vec::new();
+    let start = Instant::now();
+
+    let outcomes: Box<dyn Iterator<Item = DayOutcome>> = if options.jobs > 1 {
+        Box::new(solve_parallel(&registry, options, answers.as_ref(), &locator, days).into_iter())
+    } else {
+        Box::new(solve_sequential(
+            &registry,
+            options,
+            answers.as_ref(),
+            &locator,
+            days,
+        ))
+    };
+
+    for outcome in outcomes {
+        match outcome {
+            DayOutcome::Solved(report) => {
+                let day = report.day;
+                match options.format {
+                    OutputFormat::Text => print_report(&report),
+                    OutputFormat::Json => json_days.push(report.to_json()),
+                    OutputFormat::JsonLines => {
+                        report
+                            .parts
+                            .iter()
+                            .for_each(|p| println!("{}", p.to_json(day)));
+                    }
+                }
+                failed += report.parts.iter().filter(|p| p.result.is_err()).count();
+                mismatched += report
+                    .parts
+                    .iter()
+                    .filter(|p| matches!(p.verdict, Some(Verdict::Fail { .. })))
+                    .count();
+                recorded.extend(
+                    report
+                        .parts
+                        .into_iter()
+                        .filter_map(|p| Some((day, p.part, p.result.ok()?))),
+                );
+            }
+            DayOutcome::Missing(day, e) => {
+                match options.format {
+                    OutputFormat::Text => {
+                        println!("Day {day}: {}", title(&registry, day));
+                        println!("Skipped: {e}");
+                        println!("{SEPARATOR}");
+                    }
+                    OutputFormat::Json => json_days.push(Json::Object(vec![
+                        ("day", Json::from(day)),
+                        ("title", Json::from(title(&registry, day))),
+                        ("status", Json::from("missing_input")),
+                        ("error", Json::from(e.to_string())),
+                        ("load_ns", Json::Null),
+                        ("parse_ns", Json::Null),
+                        ("total_ns", Json::Null),
+                        ("parts", Json::Array(Vec::new())),
+                    ])),
+                    OutputFormat::JsonLines => println!("{}", missing_input_json(day, &e)),
+                }
+                missing += 1;
+            }
+        }
+    }
+
+    let total = start.elapsed();
+
+    match options.format {
+        OutputFormat::Text => println!("Total time: {total:.2?}"),
+        OutputFormat::Json => println!(
+            "{}",
+            Json::Object(vec![
+                ("days", Json::Array(json_days)),
+                ("total_ns", nanos(total)),
+            ])
+        ),
+        OutputFormat::JsonLines => {}
+    }
+
+    if options.record
+        && let Some(answers) = &mut answers
+    {
+        for (day, part, solution) in &recorded {
+            answers.record(*day, *part, solution);
+        }
+        answers.save(&options.answers)?;
+        eprintln!("Recorded answers in {}", options.answers.display());
+    }
+
+    if missing > 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("{missing} day(s) could not load their input"),
+        ));
+    }
+
+    if failed > 0 {
+        return Err(io::Error::other(format!(
+            "{failed} part(s) failed to solve"
+        )));
+    }
+
+    if options.check && mismatched > 0 {
+        return Err(io::Error::other(format!(
+            "{mismatched} answer(s) differ from the recorded ones"
+        )));
+    }
+
+    Ok(())
+}
+
+pub fn bench(options: &BenchOptions) -> io::Result<()> {
+    let registry = Registry::new().map_err(|e| io::Error::other(e.to_string()))?;
+    let days = selected_days(&registry, &options.run)?;
+    let locator = options.run.locator();
+
+    println!(
+        "Benchmarking with {} warm-up and {} timed run(s) per part",
+        options.warmup, options.runs
+    );
+    println!(
+        "{:>4} {:>5} {:>12} {:>12} {:>12}",
+        "Day", "Part", "min", "median", "max"
+    );
+
+    let mut missing = 0;
+    let mut failed = 0;
+
+    for day in days {
+        let input = match load_input(&options.run, &locator, day) {
+            Ok((input, _)) => input,
+            Err(e) => {
+                println!("{day:>4} Skipped: {e}");
+                missing += 1;
+                continue;
+            }
+        };
+
+        let parse = || {
+            registry
+                .parse(day, &input)
+                .expect("selected day has a registered solver")
+        };
+        let parsed = match parse() {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                println!("{day:>4} {:>5} Error: {e}", "parse");
+                failed += selected_parts(&options.run).count();
+                continue;
+            }
+        };
+        print_stats(day, "parse", sample(options, || drop(parse())));
+
+        for part in selected_parts(&options.run) {
+            if let Err(e) = parsed.solve(part) {
+                println!("{day:>4} {:>5} Error: {e}", part.number());
+                failed += 1;
+                continue;
+            }
+            print_stats(
+                day,
+                &part.number().to_string(),
+                sample(options, || drop(parsed.solve(part))),
+            );
+        }
+    }
+
+    if missing > 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("{missing} day(s) could not load their input"),
+        ));
+    }
+
+    if failed > 0 {
+        return Err(io::Error::other(format!(
+            "{failed} part(s) failed to solve"
+        )));
+    }
+
+    Ok(())
+}
+
+/// Runs `f` untimed for the warm-up, then once per timed run.
+fn sample(options: &BenchOptions, mut f: impl FnMut()) -> Option<Stats> {
+    for _ in 0..options.warmup {
+        f();
+    }
+
+    let samples = (0..options.runs)
+        .map(|_| {
+            let start = Instant::now();
+            f();
+            start.elapsed()
+        })
+        .collect();
+
+    Stats::new(samples)
+}
+
+fn print_stats(day: usize, step: &str, stats: Option<Stats>) {
+    if let Some(stats) = stats {
+        println!(
+            "{day:>4} {step:>5} {:>12} {:>12} {:>12}",
+            format!("{:.2?}", stats.min),
+            format!("{:.2?}", stats.median),
+            format!("{:.2?}", stats.max),
+        );
+    }
+}
+
+pub fn list(options: &RunOptions) -> io::Result<()> {
+    let registry = Registry::new().map_err(|e| io::Error::other(e.to_string()))?;
+    let days = selected_days(&registry, options)?;
+
+    println!("{:>4} {:>5}  {:<24} Tags", "Day", "Year", "Title");
+    for day in days {
+        if let Some(m) = registry.metadata(day) {
+            println!(
+                "{:>4} {:>5}  {:<24} {}",
+                m.day,
+                m.year,
+                m.title,
+                m.tags.join(", ")
+            );
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ns(n: u64) -> Duration {
+        Duration::from_nanos(n)
+    }
+
+    #[test]
+    fn test_stats_odd_samples() {
+        let stats = Stats::new(vec![ns(5), ns(1), ns(3)]);
+        assert_eq!(
+            Some(Stats {
+                min: ns(1),
+                median: ns(3),
+                max: ns(5)
+            }),
+            stats
+        );
+    }
+
+    #[test]
+    fn test_stats_even_samples() {
+        let stats = Stats::new(vec![ns(4), ns(1), ns(2), ns(8)]).unwrap();
+        assert_eq!(ns(3), stats.median);
+    }
+
+    fn report(
+        parse_error: Option<SolveError>,
+        results: Vec<Result<Solution, SolveError>>,
+    ) -> DayReport {
+        DayReport {
+            day: 9,
+            title: "Movie Theater",
+            load: Duration::ZERO,
+            parse: Duration::ZERO,
+            parse_error,
+            parts: Part::ALL
+                .into_iter()
+                .zip(results)
+                .map(|(part, result)| PartReport {
+                    part,
+                    result,
+                    elapsed: Duration::ZERO,
+                    verdict: None,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_day_status_follows_parse_and_parts() {
+        let ok = report(None, vec![Ok(Solution::new(50)), Ok(Solution::new(24))]);
+        assert_eq!(("ok", None), ok.status());
+
+        let failed = report(
+            None,
+            vec![Ok(Solution::new(50)), Err(SolveError::new("no loop"))],
+        );
+        assert_eq!(
+            ("error", Some("failed: no loop".to_string())),
+            failed.status()
+        );
+
+        let error = SolveError::at_line(1, "expected 2 coordinates, found 1");
+        let unparsed = report(Some(error.clone()), vec![Err(error.clone()), Err(error)]);
+        assert!(unparsed.to_json().to_string().contains(
+            r#""status":"parse_error","error":"at line 1 failed: expected 2 coordinates, found 1""#
+        ));
+    }
+
+    #[test]
+    fn test_stats_no_samples() {
+        assert_eq!(None, Stats::new(Vec::new()));
+    }
+}
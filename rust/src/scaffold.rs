@@ -0,0 +1,177 @@
+use std::{
+    fs::{OpenOptions, read_to_string, write},
+    io::{self, Write},
+    path::Path,
+};
+
+use rust::Registry;
+
+/// Source directory of the crate, where new days are generated.
+const SRC_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src");
+
+const DAYS_LIST: &str = "days! {";
+
+/// Creates `solutions/day_NN.rs` with a solver stub and adds it to the list
+/// of days in `solutions/mod.rs`. Existing files are never overwritten.
+pub fn new_day(day: usize) -> io::Result<()> {
+    let registry = Registry::new().map_err(|e| io::Error::other(e.to_string()))?;
+    if registry.contains(day) {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("day {day} already has a solver"),
+        ));
+    }
+
+    let next = registry.days().last().map_or(1, |last| last + 1);
+    if day > next {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("day {day} cannot be added, the next day without a solver is {next}"),
+        ));
+    }
+
+    let solutions = Path::new(SRC_DIR).join("solutions");
+    let path = solutions.join(format!("day_{day:02}.rs"));
+    let mod_path = solutions.join("mod.rs");
+    let module = register(&read_to_string(&mod_path)?, day)?;
+
+    create_new(&path, &template(day))?;
+    write(&mod_path, module)?;
+
+    println!("Created {}", path.display());
+    println!("Registered Day{day:02} in {}", mod_path.display());
+    Ok(())
+}
+
+fn create_new(path: &Path, contents: &str) -> io::Result<()> {
+    let mut file = OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)
+        .map_err(|e| match e.kind() {
+            io::ErrorKind::AlreadyExists => io::Error::new(
+                e.kind(),
+                format!(
+                    "'{}' already exists, refusing to overwrite it",
+                    path.display()
+                ),
+            ),
+            _ => e,
+        })?;
+    file.write_all(contents.as_bytes())
+}
+
+/// Adds `day_NN::DayNN` to the `days!` list of `module`, keeping it sorted.
+fn register(module: &str, day: usize) -> io::Result<String> {
+    let entry = format!("    day_{day:02}::Day{day:02},");
+    let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidData, message.to_string());
+
+    let mut lines: Vec<&str> = module.lines().collect();
+    let start = lines
+        .iter()
+        .position(|l| l.trim() == DAYS_LIST)
+        .ok_or_else(|| invalid("solutions/mod.rs has no 'days!' list"))?;
+    let end = start
+        + lines[start..]
+            .iter()
+            .position(|l| l.trim() == "}")
+            .ok_or_else(|| invalid("the 'days!' list in solutions/mod.rs is not closed"))?;
+
+    if lines[start + 1..end].contains(&entry.as_str()) {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("Day{day:02} is already registered"),
+        ));
+    }
+
+    let at = start
+        + 1
+        + lines[start + 1..end]
+            .iter()
+            .take_while(|l| **l < entry.as_str())
+            .count();
+    lines.insert(at, &entry);
+
+    Ok(lines.join("\n") + "\n")
+}
+
+fn template(day: usize) -> String {
+    format!(
+        r#"use crate::solvable::{{Metadata, Solution, Solvable, SolveError}};
+
+pub struct Day{day:02};
+
+impl Solvable for Day{day:02} {{
+    const METADATA: Metadata = Metadata {{
+        year: 2025,
+        day: {day},
+        title: "Day {day}",
+        tags: &[],
+    }};
+
+    type Input = Vec<String>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, SolveError> {{
+        Ok(input.lines().map(str::to_string).collect())
+    }}
+
+    fn first(&self, _input: &Self::Input) -> Result<Solution, SolveError> {{
+        Ok(Solution::NotImplemented)
+    }}
+}}
+
+#[cfg(test)]
+mod tests {{
+    use crate::solvable::Solution;
+
+    use super::*;
+
+    const INPUT: &str = "";
+
+    #[test]
+    fn test_day_{day:02}_first_task() {{
+        let day_{day:02} = Day{day:02};
+        let solution = day_{day:02}.parse(INPUT).and_then(|input| day_{day:02}.first(&input));
+        assert_eq!(Ok(Solution::NotImplemented), solution);
+    }}
+}}
+"#
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MODULE: &str = "macro_rules! days {}
+
+days! {
+    day_01::Day01,
+    day_02::Day02,
+    day_04::Day04,
+}
+";
+
+    #[test]
+    fn test_register_keeps_days_sorted() {
+        let module = register(MODULE, 3).unwrap();
+        assert!(module.contains("    day_02::Day02,\n    day_03::Day03,\n    day_04::Day04,\n"));
+
+        let module = register(MODULE, 5).unwrap();
+        assert!(module.contains("    day_04::Day04,\n    day_05::Day05,\n}\n"));
+    }
+
+    #[test]
+    fn test_register_refuses_duplicates() {
+        assert!(register(MODULE, 2).is_err());
+        assert!(register("pub mod day_01;", 2).is_err());
+    }
+
+    #[test]
+    fn test_template_names_the_day() {
+        let source = template(13);
+        assert!(source.contains("pub struct Day13;"));
+        assert!(source.contains("day: 13,"));
+        assert!(source.contains("fn test_day_13_first_task()"));
+    }
+}
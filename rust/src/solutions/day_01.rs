@@ -1,62 +1,97 @@
 use core::str;
 
-use crate::solvable::{Solution, Solvable};
+use crate::solvable::{Metadata, Solution, Solvable, SolveError, column_of, parse_token};
 
 pub struct Day01;
 
-struct Rotation {
-    dir: u8,
+#[derive(Debug, Clone, Copy)]
+enum Turn {
+    Left,
+    Right,
+}
+
+/// One turn of the dial, in the order given by the input.
+#[derive(Debug, Clone, Copy)]
+pub struct Rotation {
+    dir: Turn,
     dist: isize,
 }
 
 impl Solvable for Day01 {
-    fn first(&self, input: &str) -> crate::solvable::Solution {
-        let count = parse_rotations(input)
+    const METADATA: Metadata = Metadata {
+        year: 2025,
+        day: 1,
+        title: "Secret Entrance",
+        tags: &["simulation"],
+    };
+
+    type Input = Vec<Rotation>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, SolveError> {
+        parse_rotations(input)
+    }
+
+    fn first(&self, rotations: &Self::Input) -> Result<Solution, SolveError> {
+        let count = rotations
+            .iter()
             .scan(50isize, |pos, r| {
                 *pos = match r.dir {
-                    b'L' => (*pos - r.dist).rem_euclid(LOCK_SIZE),
-                    b'R' => (*pos + r.dist).rem_euclid(LOCK_SIZE),
-                    _ => unreachable!(),
+                    Turn::Left => (*pos - r.dist).rem_euclid(LOCK_SIZE),
+                    Turn::Right => (*pos + r.dist).rem_euclid(LOCK_SIZE),
                 };
                 Some(*pos)
             })
             .filter(|&pos| pos == 0)
             .count() as i128;
 
-        Solution::new(count)
+        Ok(Solution::new(count))
     }
 
-    fn second(&self, input: &str) -> crate::solvable::Solution {
-        let count = parse_rotations(input)
+    fn second(&self, rotations: &Self::Input) -> Result<Solution, SolveError> {
+        let count = rotations
+            .iter()
             .fold((50isize, 0isize), |(pos, total), r| {
-                let hits = hits_zero_during_rotation(pos, &r);
+                let hits = hits_zero_during_rotation(pos, r);
 
                 let new_pos = match r.dir {
-                    b'R' => (pos + r.dist).rem_euclid(LOCK_SIZE),
-                    b'L' => (pos - r.dist).rem_euclid(LOCK_SIZE),
-                    _ => unreachable!(),
+                    Turn::Right => (pos + r.dist).rem_euclid(LOCK_SIZE),
+                    Turn::Left => (pos - r.dist).rem_euclid(LOCK_SIZE),
                 };
 
                 (new_pos, total + hits)
             })
             .1 as i128;
 
-        Solution::new(count)
+        Ok(Solution::new(count))
     }
 }
 
-fn parse_rotations(input: &str) -> impl Iterator<Item = Rotation> + '_ {
+fn parse_rotations(input: &str) -> Result<Vec<Rotation>, SolveError> {
     input
         .lines()
-        .map(str::trim)
-        .filter(|l| !l.is_empty())
-        .map(|line| {
-            let (d, n) = line.split_at(1);
-            Rotation {
-                dir: d.as_bytes()[0],
-                dist: n.parse().unwrap(),
-            }
+        .enumerate()
+        .map(|(i, line)| (i + 1, line, line.trim()))
+        .filter(|(_, _, l)| !l.is_empty())
+        .map(|(line_no, raw, line)| {
+            let (d, n) = line.split_at(line.ceil_char_boundary(1));
+            let dir = match d {
+                "L" => Turn::Left,
+                "R" => Turn::Right,
+                _ => {
+                    return Err(SolveError::at(
+                        line_no,
+                        column_of(raw, d),
+                        format!("expected 'L' or 'R', found '{d}'"),
+                    ));
+                }
+            };
+
+            Ok(Rotation {
+                dir,
+                dist: parse_token(line_no, raw, n)?,
+            })
         })
+        .collect()
 }
 
 fn hits_zero_during_rotation(pos: isize, rotation: &Rotation) -> isize {
@@ -65,9 +100,8 @@ fn hits_zero_during_rotation(pos: isize, rotation: &Rotation) -> isize {
     }
 
     let target = match rotation.dir {
-        b'R' => (-pos).rem_euclid(LOCK_SIZE),
-        b'L' => pos.rem_euclid(LOCK_SIZE),
-        _ => unreachable!(),
+        Turn::Right => (-pos).rem_euclid(LOCK_SIZE),
+        Turn::Left => pos.rem_euclid(LOCK_SIZE),
     };
 
     let first = if target == 0 { LOCK_SIZE } else { target };
@@ -101,14 +135,26 @@ L82";
     #[test]
     fn test_day_01_first_task() {
         let day_01 = Day01;
-        let solution = day_01.first(INPUT);
-        assert_eq!(Solution::new(3), solution);
+        let solution = day_01.parse(INPUT).and_then(|input| day_01.first(&input));
+        assert_eq!(Ok(Solution::new(3)), solution);
     }
 
     #[test]
     fn test_day_01_second_task() {
         let day_01 = Day01;
-        let solution = day_01.second(INPUT);
-        assert_eq!(Solution::new(6), solution);
+        let solution = day_01.parse(INPUT).and_then(|input| day_01.second(&input));
+        assert_eq!(Ok(Solution::new(6)), solution);
+    }
+
+    #[test]
+    fn test_day_01_malformed_rotation() {
+        let day_01 = Day01;
+        let solution = day_01
+            .parse("L68\nX30")
+            .and_then(|input| day_01.first(&input));
+        assert_eq!(
+            Err(SolveError::at(2, 1, "expected 'L' or 'R', found 'X'")),
+            solution
+        );
     }
 }
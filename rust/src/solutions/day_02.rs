@@ -1,6 +1,8 @@
-use crate::solvable::{Solution, Solvable};
+use crate::solvable::{Metadata, Solution, Solvable, SolveError, column_of, parse_token};
 
-struct Range {
+/// An inclusive range of product IDs.
+#[derive(Debug, Clone, Copy)]
+pub struct Range {
     min: i64,
     max: i64,
 }
@@ -8,42 +10,65 @@ struct Range {
 pub struct Day02;
 
 impl Solvable for Day02 {
-    fn first(&self, input: &str) -> crate::solvable::Solution {
-        let sum = parse_ranges(input)
+    const METADATA: Metadata = Metadata {
+        year: 2025,
+        day: 2,
+        title: "Gift Shop",
+        tags: &["math"],
+    };
+
+    type Input = Vec<Range>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, SolveError> {
+        parse_ranges(input)
+    }
+
+    fn first(&self, ranges: &Self::Input) -> Result<Solution, SolveError> {
+        let sum = ranges
+            .iter()
             .flat_map(|r| r.min..=r.max)
             .filter(|&n| is_double_repeat(n))
             .sum::<i64>();
 
-        Solution::new(i128::from(sum))
+        Ok(Solution::new(i128::from(sum)))
     }
 
-    fn second(&self, input: &str) -> crate::solvable::Solution {
-        let sum = parse_ranges(input)
+    fn second(&self, ranges: &Self::Input) -> Result<Solution, SolveError> {
+        let sum = ranges
+            .iter()
             .flat_map(|r| r.min..=r.max)
             .filter(|&n| is_repeated_at_least_twice(n))
             .sum::<i64>();
 
-        Solution::new(i128::from(sum))
+        Ok(Solution::new(i128::from(sum)))
     }
 }
 
-fn parse_ranges(input: &str) -> impl Iterator<Item = Range> + '_ {
+fn parse_ranges(input: &str) -> Result<Vec<Range>, SolveError> {
     input
-        .trim()
-        .split(',')
-        .filter(|s| !s.is_empty())
-        .map(|range| {
-            let (a, b) = range.split_once('-').expect("Each range must contain '-'");
-            Range {
-                min: a.parse().unwrap(),
-                max: b.parse().unwrap(),
-            }
+        .lines()
+        .enumerate()
+        .flat_map(|(i, line)| line.split(',').map(move |range| (i + 1, line, range)))
+        .filter(|(_, _, range)| !range.trim().is_empty())
+        .map(|(line_no, line, range)| {
+            let (a, b) = range.split_once('-').ok_or_else(|| {
+                SolveError::at(
+                    line_no,
+                    column_of(line, range),
+                    format!("range '{}' must contain '-'", range.trim()),
+                )
+            })?;
+            Ok(Range {
+                min: parse_token(line_no, line, a)?,
+                max: parse_token(line_no, line, b)?,
+            })
         })
+        .collect()
 }
 
 fn is_double_repeat(n: i64) -> bool {
     let s = n.to_string();
-    if s.len() % 2 != 0 {
+    if !s.len().is_multiple_of(2) {
         return false;
     }
     let mid = s.len() / 2;
@@ -54,7 +79,7 @@ fn is_repeated_at_least_twice(n: i64) -> bool {
     let s = n.to_string();
 
     (1..=s.len() / 2)
-        .filter(|&pat_len| s.len() % pat_len == 0)
+        .filter(|&pat_len| s.len().is_multiple_of(pat_len))
         .any(|pat_len| {
             let reps = s.len() / pat_len;
             let pat = &s[..pat_len];
@@ -73,14 +98,14 @@ mod tests {
     #[test]
     fn test_day_02_first_task() {
         let day_02 = Day02;
-        let solution = day_02.first(INPUT);
-        assert_eq!(Solution::new(1_227_775_554), solution);
+        let solution = day_02.parse(INPUT).and_then(|input| day_02.first(&input));
+        assert_eq!(Ok(Solution::new(1_227_775_554)), solution);
     }
 
     #[test]
     fn test_day_02_second_task() {
         let day_02 = Day02;
-        let solution = day_02.second(INPUT);
-        assert_eq!(Solution::new(4_174_379_265), solution);
+        let solution = day_02.parse(INPUT).and_then(|input| day_02.second(&input));
+        assert_eq!(Ok(Solution::new(4_174_379_265)), solution);
     }
 }
@@ -1,19 +1,61 @@
-use crate::solvable::{Solution, Solvable};
+use crate::solvable::{Metadata, Solution, Solvable, SolveError};
 
 pub struct Day03;
 
 impl Solvable for Day03 {
-    fn first(&self, input: &str) -> crate::solvable::Solution {
-        let solution = input.lines().map(max_pair_value).sum();
+    const METADATA: Metadata = Metadata {
+        year: 2025,
+        day: 3,
+        title: "Lobby",
+        tags: &["greedy"],
+    };
+
+    /// One bank of batteries per line, as their digits.
+    type Input = Vec<String>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, SolveError> {
+        parse_banks(input)
+    }
+
+    fn first(&self, banks: &Self::Input) -> Result<Solution, SolveError> {
+        let solution = with_batteries(banks, 2)?.map(max_pair_value).sum();
+
+        Ok(Solution::new(solution))
+    }
 
-        Solution::new(solution)
+    fn second(&self, banks: &Self::Input) -> Result<Solution, SolveError> {
+        let solution = with_batteries(banks, 12)?.map(max_12).sum();
+
+        Ok(Solution::new(solution))
     }
+}
 
-    fn second(&self, input: &str) -> crate::solvable::Solution {
-        let solution = input.lines().map(max_12).sum();
+/// Validates that every bank consists of digits only.
+fn parse_banks(input: &str) -> Result<Vec<String>, SolveError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| match line.bytes().position(|b| !b.is_ascii_digit()) {
+            Some(col) => Err(SolveError::at(i + 1, col + 1, "expected a digit")),
+            None => Ok(line.to_string()),
+        })
+        .collect()
+}
 
-        Solution::new(solution)
+/// Checks that every bank holds at least `min_len` batteries, which differs
+/// between the parts.
+fn with_batteries(
+    banks: &[String],
+    min_len: usize,
+) -> Result<impl Iterator<Item = &str>, SolveError> {
+    if let Some(i) = banks.iter().position(|bank| bank.len() < min_len) {
+        return Err(SolveError::at_line(
+            i + 1,
+            format!("bank needs at least {min_len} batteries"),
+        ));
     }
+
+    Ok(banks.iter().map(String::as_str))
 }
 
 fn max_pair_value(line: &str) -> i128 {
@@ -54,10 +96,9 @@ fn max_12(line: &str) -> i128 {
 
     stack.truncate(12);
 
-    String::from_utf8(stack)
-        .expect("only digits")
-        .parse::<i128>()
-        .unwrap()
+    stack
+        .iter()
+        .fold(0i128, |acc, &b| acc * 10 + i128::from(b - b'0'))
 }
 
 #[cfg(test)]
@@ -74,14 +115,14 @@ mod tests {
     #[test]
     fn test_day_03_first_task() {
         let day_03 = Day03;
-        let solution = day_03.first(INPUT);
-        assert_eq!(Solution::new(357), solution);
+        let solution = day_03.parse(INPUT).and_then(|input| day_03.first(&input));
+        assert_eq!(Ok(Solution::new(357)), solution);
     }
 
     #[test]
     fn test_day_03_second_task() {
         let day_03 = Day03;
-        let solution = day_03.second(INPUT);
-        assert_eq!(Solution::new(3_121_910_778_619), solution);
+        let solution = day_03.parse(INPUT).and_then(|input| day_03.second(&input));
+        assert_eq!(Ok(Solution::new(3_121_910_778_619)), solution);
     }
 }
@@ -1,81 +1,79 @@
-use std::collections::HashSet;
-
 use crate::{
+    grid::Grid,
     point::Point2D,
-    solvable::{Solution, Solvable},
+    solvable::{Metadata, Solution, Solvable, SolveError},
 };
 
 pub struct Day04;
 
 impl Solvable for Day04 {
-    fn first(&self, input: &str) -> crate::solvable::Solution {
-        let paper = Paper::new(input);
-        let solution = paper
-            .0
-            .iter()
-            .filter(|&&roll| {
-                roll.neighbors()
-                    .iter()
-                    .filter(|&n| paper.0.contains(n))
-                    .count()
-                    < 4
-            })
-            .count() as i128;
-
-        Solution::new(solution)
+    const METADATA: Metadata = Metadata {
+        year: 2025,
+        day: 4,
+        title: "Printing Department",
+        tags: &["grid"],
+    };
+
+    type Input = Paper;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, SolveError> {
+        Paper::new(input)
+    }
+
+    fn first(&self, paper: &Self::Input) -> Result<Solution, SolveError> {
+        let solution = paper.rolls().filter(|&p| paper.is_accessible(p)).count() as i128;
+
+        Ok(Solution::new(solution))
     }
 
-    fn second(&self, input: &str) -> crate::solvable::Solution {
-        let paper = Paper::new(input);
-        let mut rolls = paper.0.clone();
+    fn second(&self, paper: &Self::Input) -> Result<Solution, SolveError> {
+        let mut paper = paper.clone();
+        let mut removed = 0;
 
         loop {
-            let next: HashSet<Point2D> = rolls
-                .iter()
-                .copied()
-                .filter(|roll| {
-                    roll.neighbors()
-                        .iter()
-                        .filter(|&n| rolls.contains(n))
-                        .count()
-                        >= 4
-                })
-                .collect();
-
-            if next.len() == rolls.len() {
+            let accessible: Vec<Point2D> =
+                paper.rolls().filter(|&p| paper.is_accessible(p)).collect();
+
+            if accessible.is_empty() {
                 break;
             }
 
-            rolls = next;
+            for &p in &accessible {
+                paper.0[p] = false;
+            }
+            removed += accessible.len();
         }
 
-        let solution = (paper.0.len() - rolls.len()) as i128;
-
-        Solution::new(solution)
+        Ok(Solution::from(removed))
     }
 }
 
-struct Paper(HashSet<Point2D>);
+/// Where the rolls of paper are.
+#[derive(Debug, Clone)]
+pub struct Paper(Grid<bool>);
 
 impl Paper {
-    fn new(input: &str) -> Self {
-        let set = input
-            .lines()
-            .enumerate()
-            .flat_map(|(x, row)| {
-                row.bytes().enumerate().filter_map(move |(y, b)| {
-                    if b == b'@' {
-                        Some(Point2D {
-                            x: i32::try_from(x).unwrap(),
-                            y: i32::try_from(y).unwrap(),
-                        })
-                    } else {
-                        None
-                    }
-                })
-            })
-            .collect();
-        Self(set)
+    fn new(input: &str) -> Result<Self, SolveError> {
+        let grid = Grid::parse(input)?;
+
+        if let Some((p, &b)) = grid.iter().find(|&(_, &b)| b != b'@' && b != b'.') {
+            return Err(SolveError::at(
+                p.y as usize + 1,
+                p.x as usize + 1,
+                format!("unexpected cell '{}'", b as char),
+            ));
+        }
+
+        Ok(Self(grid.map(|&b| b == b'@')))
+    }
+
+    fn rolls(&self) -> impl Iterator<Item = Point2D> + '_ {
+        self.0.iter().filter(|&(_, &roll)| roll).map(|(p, _)| p)
+    }
+
+    /// A forklift can reach a roll with fewer than four rolls around it.
+    fn is_accessible(&self, p: Point2D) -> bool {
+        self.0.neighbors(p).filter(|&(_, &roll)| roll).count() < 4
     }
 }
 
@@ -99,14 +97,14 @@ mod tests {
     #[test]
     fn test_day_04_first_task() {
         let day_04 = Day04;
-        let solution = day_04.first(INPUT);
-        assert_eq!(Solution::new(13), solution);
+        let solution = day_04.parse(INPUT).and_then(|input| day_04.first(&input));
+        assert_eq!(Ok(Solution::new(13)), solution);
     }
 
     #[test]
     fn test_day_04_second_task() {
         let day_04 = Day04;
-        let solution = day_04.second(INPUT);
-        assert_eq!(Solution::new(43), solution);
+        let solution = day_04.parse(INPUT).and_then(|input| day_04.second(&input));
+        assert_eq!(Ok(Solution::new(43)), solution);
     }
 }
@@ -1,29 +1,76 @@
-use crate::solvable::{Solution, Solvable};
+use crate::solvable::{Metadata, Solution, Solvable, SolveError, parse_token};
 
 pub struct Day05;
 
 impl Solvable for Day05 {
-    fn first(&self, input: &str) -> crate::solvable::Solution {
-        let (ranges, available) = input.split_once("\n\n").unwrap();
-        let ranges = Ranges::new_from_lines(ranges).merged();
+    const METADATA: Metadata = Metadata {
+        year: 2025,
+        day: 5,
+        title: "Cafeteria",
+        tags: &["ranges"],
+    };
 
-        let solution = available
-            .lines()
-            .filter_map(|s| s.parse::<usize>().ok())
-            .filter(|&id| ranges.contains(id))
+    type Input = Inventory;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, SolveError> {
+        Inventory::parse(input)
+    }
+
+    fn first(&self, inventory: &Self::Input) -> Result<Solution, SolveError> {
+        let solution = inventory
+            .ids
+            .iter()
+            .filter(|&&id| inventory.fresh.contains(id))
             .count() as i128;
 
-        Solution::new(solution)
+        Ok(Solution::new(solution))
+    }
+
+    fn second(&self, inventory: &Self::Input) -> Result<Solution, SolveError> {
+        let solution = inventory.fresh.count() as i128;
+
+        Ok(Solution::new(solution))
     }
+}
+
+/// The merged fresh ingredient ranges and the available ingredient ids.
+#[derive(Debug, Clone)]
+pub struct Inventory {
+    fresh: Ranges,
+    ids: Vec<usize>,
+}
+
+impl Inventory {
+    fn parse(input: &str) -> Result<Self, SolveError> {
+        let (ranges, available) = split_sections(input)?;
+        let first_id_line = ranges.lines().count() + 2;
+        let fresh = Ranges::new_from_lines(ranges)?.merged();
 
-    fn second(&self, input: &str) -> crate::solvable::Solution {
-        let (ranges, _) = input.split_once("\n\n").unwrap();
-        let ranges = Ranges::new_from_lines(ranges).merged();
+        let ids = available
+            .lines()
+            .enumerate()
+            .filter(|(_, l)| !l.trim().is_empty())
+            .map(|(i, l)| parse_token::<usize>(first_id_line + i, l, l))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self { fresh, ids })
+    }
+}
 
-        let solution = ranges.count() as i128;
+/// Splits the input at its first blank line, whatever its line endings.
+fn split_sections(input: &str) -> Result<(&str, &str), SolveError> {
+    let mut offset = 0;
 
-        Solution::new(solution)
+    for line in input.split_inclusive('\n') {
+        if line.trim().is_empty() {
+            return Ok((&input[..offset], &input[offset + line.len()..]));
+        }
+        offset += line.len();
     }
+
+    Err(SolveError::new(
+        "expected a blank line between the ranges and the available ids",
+    ))
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -42,20 +89,31 @@ impl Range {
 struct Ranges(Vec<Range>);
 
 impl Ranges {
-    fn new_from_lines(input: &str) -> Self {
+    fn new_from_lines(input: &str) -> Result<Self, SolveError> {
         let v = input
             .lines()
-            .map(|line| {
-                let (from_s, to_s) = line.split_once('-').unwrap();
-
-                let from = from_s.parse::<usize>().unwrap();
-                let to = to_s.parse::<usize>().unwrap();
-
-                Range { from, to }
+            .enumerate()
+            .map(|(i, line)| {
+                let line_no = i + 1;
+                let (from_s, to_s) = line.split_once('-').ok_or_else(|| {
+                    SolveError::at_line(line_no, format!("range '{line}' must contain '-'"))
+                })?;
+
+                let from = parse_token(line_no, line, from_s)?;
+                let to = parse_token(line_no, line, to_s)?;
+
+                if from > to {
+                    return Err(SolveError::at_line(
+                        line_no,
+                        format!("range '{line}' ends before it starts"),
+                    ));
+                }
+
+                Ok(Range { from, to })
             })
-            .collect();
+            .collect::<Result<_, _>>()?;
 
-        Self(v)
+        Ok(Self(v))
     }
 
     fn merged(mut self) -> Self {
@@ -113,14 +171,22 @@ mod tests {
     #[test]
     fn test_day_05_first_task() {
         let day_05 = Day05;
-        let solution = day_05.first(INPUT);
-        assert_eq!(Solution::new(3), solution);
+        let solution = day_05.parse(INPUT).and_then(|input| day_05.first(&input));
+        assert_eq!(Ok(Solution::new(3)), solution);
     }
 
     #[test]
     fn test_day_05_second_task() {
         let day_05 = Day05;
-        let solution = day_05.second(INPUT);
-        assert_eq!(Solution::new(14), solution);
+        let solution = day_05.parse(INPUT).and_then(|input| day_05.second(&input));
+        assert_eq!(Ok(Solution::new(14)), solution);
+    }
+
+    #[test]
+    fn test_day_05_crlf_line_endings() {
+        let day_05 = Day05;
+        let input = INPUT.replace('\n', "\r\n");
+        let solution = day_05.parse(&input).and_then(|input| day_05.first(&input));
+        assert_eq!(Ok(Solution::new(3)), solution);
     }
 }
@@ -1,29 +1,62 @@
-use crate::solvable::{Solution, Solvable};
+use crate::{
+    grid::Grid,
+    inputs::InputMode,
+    solvable::{Metadata, RawSolvable, Solution, SolveError, column_of, parse_token},
+};
 
 pub struct Day06;
 
-impl Solvable for Day06 {
-    fn first(&self, input: &str) -> crate::solvable::Solution {
+impl RawSolvable for Day06 {
+    const METADATA: Metadata = Metadata {
+        year: 2025,
+        day: 6,
+        title: "Trash Compactor",
+        tags: &["parsing"],
+    };
+
+    // The second part reads numbers down the columns, so spaces matter.
+    const INPUT_MODE: InputMode = InputMode::Raw;
+
+    fn first(&self, input: &str) -> Result<Solution, SolveError> {
         let mut lines: Vec<&str> = input.lines().collect();
-        let op_line = lines.pop().unwrap();
+        let op_line = lines
+            .pop()
+            .ok_or_else(|| SolveError::new("worksheet is empty"))?;
+        let op_line_no = lines.len() + 1;
 
         let ops: Vec<Op> = op_line
             .split_whitespace()
             .map(|s| match s {
-                "+" => Op::Add,
-                "*" => Op::Mul,
-                _ => unreachable!(),
+                "+" => Ok(Op::Add),
+                "*" => Ok(Op::Mul),
+                _ => Err(SolveError::at(
+                    op_line_no,
+                    column_of(op_line, s),
+                    format!("unknown operator '{s}'"),
+                )),
             })
-            .collect();
-
-        let columns: Vec<Vec<i128>> = (0..)
-            .map(|i| {
-                lines
-                    .iter()
-                    .map(|line| line.split_whitespace().nth(i).unwrap().parse().unwrap())
-                    .collect()
+            .collect::<Result<_, _>>()?;
+
+        let rows: Vec<Vec<i128>> = lines
+            .iter()
+            .enumerate()
+            .map(|(i, line)| {
+                let row = line
+                    .split_whitespace()
+                    .map(|token| parse_token(i + 1, line, token))
+                    .collect::<Result<Vec<_>, _>>()?;
+                if row.len() != ops.len() {
+                    return Err(SolveError::at_line(
+                        i + 1,
+                        format!("expected {} numbers, found {}", ops.len(), row.len()),
+                    ));
+                }
+                Ok(row)
             })
-            .take(ops.len())
+            .collect::<Result<_, _>>()?;
+
+        let columns: Vec<Vec<i128>> = (0..ops.len())
+            .map(|i| rows.iter().map(|row| row[i]).collect())
             .collect();
 
         let problems = ops
@@ -40,13 +73,13 @@ impl Solvable for Day06 {
             })
             .sum();
 
-        Solution::new(solution)
+        Ok(Solution::new(solution))
     }
 
-    fn second(&self, input: &str) -> crate::solvable::Solution {
-        let worksheet = Worksheet::parse(input);
+    fn second(&self, input: &str) -> Result<Solution, SolveError> {
+        let worksheet = Worksheet::parse(input)?;
 
-        Solution::new(worksheet.solve())
+        Ok(Solution::new(worksheet.solve()?))
     }
 }
 
@@ -75,59 +108,53 @@ struct Problem {
 }
 
 struct Worksheet {
-    grid: Vec<Vec<u8>>,
+    grid: Grid<u8>,
     op_row: usize,
     problems: Vec<Problem>,
 }
 
 impl Worksheet {
-    fn parse(input: &str) -> Self {
-        let lines: Vec<&str> = input.lines().collect();
-        let height = lines.len();
-        let width = lines.iter().map(|l| l.len()).max().unwrap_or(0);
-        let op_row = height - 1;
-
-        let mut grid: Vec<Vec<u8>> = Vec::with_capacity(height);
-
-        for line in &lines {
-            let mut row = vec![b' '; width];
-            row[..line.len()].copy_from_slice(line.as_bytes());
-            grid.push(row);
-        }
+    fn parse(input: &str) -> Result<Self, SolveError> {
+        let grid = Grid::parse_padded(input, b' ');
+        let op_row = grid
+            .height()
+            .checked_sub(1)
+            .ok_or_else(|| SolveError::new("worksheet is empty"))?;
 
-        let ranges = Self::find_problem_ranges(&grid, width, height);
-        let problems = Self::build_problems(&grid, op_row, &ranges);
+        let ranges = Self::find_problem_ranges(&grid);
+        let problems = Self::build_problems(&grid, op_row, &ranges)?;
 
-        Self {
+        Ok(Self {
             grid,
             op_row,
             problems,
-        }
+        })
     }
 
-    fn solve(&self) -> i128 {
+    fn solve(&self) -> Result<i128, SolveError> {
         self.problems.iter().map(|p| self.evaluate_problem(p)).sum()
     }
 
-    fn evaluate_problem(&self, p: &Problem) -> i128 {
-        let values = self.read_cephalopod_numbers(p.cols);
+    fn evaluate_problem(&self, p: &Problem) -> Result<i128, SolveError> {
+        let values = self.read_cephalopod_numbers(p.cols)?;
 
-        match p.op {
+        Ok(match p.op {
             Op::Add => values.into_iter().sum(),
             Op::Mul => values.into_iter().product::<i128>(),
-        }
+        })
     }
 
-    fn is_separator_col(grid: &[Vec<u8>], height: usize, col: usize) -> bool {
-        (0..height).all(|r| grid[r][col] == b' ')
+    fn is_separator_col(grid: &Grid<u8>, col: usize) -> bool {
+        grid.column(col).all(|&b| b == b' ')
     }
 
-    fn find_problem_ranges(grid: &[Vec<u8>], width: usize, height: usize) -> Vec<ColRange> {
+    fn find_problem_ranges(grid: &Grid<u8>) -> Vec<ColRange> {
+        let width = grid.width();
         let mut ranges = Vec::new();
         let mut start = 0usize;
 
         for c in 0..width {
-            if Self::is_separator_col(grid, height, c) {
+            if Self::is_separator_col(grid, c) {
                 if start < c {
                     ranges.push(ColRange { start, end: c });
                 }
@@ -142,53 +169,70 @@ impl Worksheet {
         ranges
     }
 
-    fn build_problems(grid: &[Vec<u8>], op_row: usize, ranges: &[ColRange]) -> Vec<Problem> {
+    fn build_problems(
+        grid: &Grid<u8>,
+        op_row: usize,
+        ranges: &[ColRange],
+    ) -> Result<Vec<Problem>, SolveError> {
         let mut problems = Vec::with_capacity(ranges.len());
 
         for &cols in ranges {
-            let slice = &grid[op_row][cols.start..cols.end];
+            let slice = &grid.row(op_row)[cols.start..cols.end];
             let op = if slice.contains(&b'+') {
                 Op::Add
             } else if slice.contains(&b'*') {
                 Op::Mul
             } else {
-                panic!(
-                    "No operator found in problem range [{}, {}]",
-                    cols.start, cols.end
-                );
+                return Err(SolveError::at(
+                    op_row + 1,
+                    cols.start + 1,
+                    format!(
+                        "no operator found in problem columns {}..{}",
+                        cols.start + 1,
+                        cols.end
+                    ),
+                ));
             };
 
             problems.push(Problem { cols, op });
         }
 
-        problems
+        Ok(problems)
     }
 
-    fn read_cephalopod_numbers(&self, cols: ColRange) -> Vec<i128> {
+    fn read_cephalopod_numbers(&self, cols: ColRange) -> Result<Vec<i128>, SolveError> {
         let mut numbers = Vec::new();
 
         for c in (cols.start..cols.end).rev() {
-            let n = self.read_number_from_column(c);
+            let n = self.read_number_from_column(c)?;
             numbers.push(n);
         }
 
-        numbers
+        Ok(numbers)
     }
 
-    fn read_number_from_column(&self, col: usize) -> i128 {
+    fn read_number_from_column(&self, col: usize) -> Result<i128, SolveError> {
         let mut digits: Vec<u8> = Vec::new();
 
-        for r in 0..self.op_row {
-            let b = self.grid[r][col];
+        for (r, &b) in self.grid.column(col).take(self.op_row).enumerate() {
             if b.is_ascii_digit() {
                 digits.push(b);
+            } else if b != b' ' {
+                return Err(SolveError::at(
+                    r + 1,
+                    col + 1,
+                    format!("unexpected character '{}'", b as char),
+                ));
             }
         }
 
-        str::from_utf8(&digits)
-            .expect("Digits are ASCII")
-            .parse::<i128>()
-            .expect("Column digits form a valid integer")
+        if digits.is_empty() {
+            return Err(SolveError::at(1, col + 1, "column holds no digits"));
+        }
+
+        Ok(digits
+            .iter()
+            .fold(0i128, |acc, &d| acc * 10 + i128::from(d - b'0')))
     }
 }
 
@@ -207,13 +251,13 @@ mod tests {
     fn test_day_06_first_task() {
         let day_06 = Day06;
         let solution = day_06.first(INPUT);
-        assert_eq!(Solution::new(4_277_556), solution);
+        assert_eq!(Ok(Solution::new(4_277_556)), solution);
     }
 
     #[test]
     fn test_day_06_second_task() {
         let day_06 = Day06;
         let solution = day_06.second(INPUT);
-        assert_eq!(Solution::new(3_263_827), solution);
+        assert_eq!(Ok(Solution::new(3_263_827)), solution);
     }
 }
@@ -1,177 +1,148 @@
 use std::collections::{HashSet, VecDeque};
 
-use crate::solvable::{Solution, Solvable};
+use num_bigint::BigUint;
+
+use crate::{
+    grid::Grid,
+    point::{Direction, Point2D},
+    solvable::{Metadata, Solution, Solvable, SolveError},
+};
 
 pub struct Day07;
 
 impl Solvable for Day07 {
-    fn first(&self, input: &str) -> crate::solvable::Solution {
-        let mut simulation = Simulation::parse(input);
+    const METADATA: Metadata = Metadata {
+        year: 2025,
+        day: 7,
+        title: "Laboratories",
+        tags: &["grid", "dynamic-programming"],
+    };
+
+    type Input = Manifold;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, SolveError> {
+        Manifold::parse(input)
+    }
 
-        for r in (simulation.start_row + 1)..simulation.h {
-            simulation.resolve_row_splitters(r);
+    fn first(&self, manifold: &Self::Input) -> Result<Solution, SolveError> {
+        let mut active_beams = HashSet::from([manifold.start.x]);
+        let mut split_count = 0;
 
-            if simulation.active_beams.is_empty() {
+        for y in (manifold.start.y + 1)..manifold.height() {
+            split_count += manifold.resolve_row_splitters(y, &mut active_beams);
+
+            if active_beams.is_empty() {
                 break;
             }
         }
 
-        Solution::new(simulation.split_count)
+        Ok(Solution::new(split_count))
     }
 
-    fn second(&self, input: &str) -> crate::solvable::Solution {
-        let mut finished = 0i128;
-        let mut simulation = Simulation::parse(input);
+    fn second(&self, manifold: &Self::Input) -> Result<Solution, SolveError> {
+        let mut finished = BigUint::ZERO;
+        let mut active_timelines = vec![BigUint::ZERO; manifold.grid.width()];
+        active_timelines[manifold.start.x as usize] = BigUint::from(1u8);
 
-        for r in (simulation.start_row + 1)..simulation.h {
-            simulation.resolve_row_splitters_quantum(r, &mut finished);
+        for y in (manifold.start.y + 1)..manifold.height() {
+            manifold.resolve_row_splitters_quantum(y, &mut active_timelines, &mut finished);
 
-            if simulation.active_timelines.iter().all(|&x| x == 0) {
+            if active_timelines.iter().all(|x| *x == BigUint::ZERO) {
                 break;
             }
         }
 
-        Solution::new(finished + simulation.active_timelines.iter().copied().sum::<i128>())
+        Ok(Solution::from(
+            finished + active_timelines.iter().sum::<BigUint>(),
+        ))
     }
 }
 
+/// The manifold diagram with the position where the beam enters it.
 #[derive(Debug)]
-struct Simulation {
-    cells: Vec<Vec<u8>>,
-    h: usize,
-    w: usize,
-    start_row: usize,
-    active_beams: HashSet<usize>,
-    active_timelines: Vec<i128>,
-    split_count: i128,
+pub struct Manifold {
+    grid: Grid<u8>,
+    start: Point2D,
 }
 
-impl Simulation {
-    fn parse(input: &str) -> Self {
-        let cells: Vec<Vec<u8>> = input.lines().map(|l| l.as_bytes().to_vec()).collect();
-
-        let h = cells.len();
-        let w = cells.first().map_or(0, Vec::len);
-
-        let mut start_row = None;
-        let mut start_col = None;
-
-        for (r, row) in cells.iter().enumerate() {
-            assert!(row.len() == w);
+impl Manifold {
+    fn parse(input: &str) -> Result<Self, SolveError> {
+        let grid = Grid::parse(input)?;
+        let start = grid
+            .find(&b'S')
+            .ok_or_else(|| SolveError::new("no 'S' found in the manifold"))?;
 
-            if let Some(c) = row.iter().position(|&b| b == b'S') {
-                start_row = Some(r);
-                start_col = Some(c);
-                break;
-            }
-        }
-
-        let (start_row, start_col) = (
-            start_row.expect("No 'S' found"),
-            start_col.expect("No 'S' found"),
-        );
-
-        let active_beams = HashSet::from_iter([start_col]);
-        let mut active_timelines = vec![0i128; w];
-        active_timelines[start_col] = 1;
-
-        Self {
-            cells,
-            h,
-            w,
-            start_row,
-            active_beams,
-            active_timelines,
-            split_count: 0,
-        }
+        Ok(Self { grid, start })
     }
 
-    fn at(&self, r: usize, c: usize) -> u8 {
-        self.cells[r][c]
+    fn height(&self) -> i32 {
+        self.grid.height() as i32
     }
 
-    fn in_bounds_col(&self, c: isize) -> bool {
-        c >= 0 && (c as usize) < self.w
+    fn is_splitter(&self, p: Point2D) -> bool {
+        self.grid.get(p) == Some(&b'^')
     }
 
-    fn resolve_row_splitters(&mut self, r: usize) {
+    /// Splits every beam that hits a splitter in row `y` and returns how many
+    /// splits happened. Beams are tracked by their column.
+    fn resolve_row_splitters(&self, y: i32, active_beams: &mut HashSet<i32>) -> i128 {
+        let mut split_count = 0;
+
         loop {
-            let splitter_col = self
-                .active_beams
+            let splitter = active_beams
                 .iter()
-                .copied()
-                .find(|&c| self.at(r, c) == b'^');
-
-            let Some(c) = splitter_col else { break };
+                .map(|&x| Point2D::new(x, y))
+                .find(|&p| self.is_splitter(p));
 
-            self.active_beams.remove(&c);
+            let Some(p) = splitter else { break };
 
-            self.split_count += 1;
+            active_beams.remove(&p.x);
 
-            let left = c as isize - 1;
-            let right = c as isize + 1;
+            split_count += 1;
 
-            if self.in_bounds_col(left) {
-                self.active_beams.insert(left as usize);
-            }
-            if self.in_bounds_col(right) {
-                self.active_beams.insert(right as usize);
-            }
-        }
-    }
-
-    fn resolve_row_splitters_quantum(&mut self, r: usize, finished: &mut i128) {
-        let mut queue: VecDeque<usize> = VecDeque::new();
-
-        for c in 0..self.w {
-            if self.active_timelines[c] > 0 && self.at(r, c) == b'^' {
-                queue.push_back(c);
+            for side in [Direction::West, Direction::East] {
+                let next = p.step(side);
+                if self.grid.contains(next) {
+                    active_beams.insert(next.x);
+                }
             }
         }
 
-        while let Some(c) = queue.pop_front() {
-            let t = self.active_timelines[c];
-            if t == 0 {
-                continue;
-            }
+        split_count
+    }
 
-            if self.at(r, c) != b'^' {
+    fn resolve_row_splitters_quantum(
+        &self,
+        y: i32,
+        active_timelines: &mut [BigUint],
+        finished: &mut BigUint,
+    ) {
+        let mut queue: VecDeque<Point2D> = active_timelines
+            .iter()
+            .enumerate()
+            .map(|(x, t)| (Point2D::new(x as i32, y), t))
+            .filter(|&(p, t)| *t > BigUint::ZERO && self.is_splitter(p))
+            .map(|(p, _)| p)
+            .collect();
+
+        while let Some(p) = queue.pop_front() {
+            if active_timelines[p.x as usize] == BigUint::ZERO {
                 continue;
             }
 
-            self.active_timelines[c] = 0;
-
-            let left = c as isize - 1;
-            let right = c as isize + 1;
-
-            if self.in_bounds_col(left) {
-                let lc = left as usize;
-                self.active_timelines[lc] = self.active_timelines[lc]
-                    .checked_add(t)
-                    .expect("Timeline count overflow. Use BigUint");
-
-                if self.at(r, lc) == b'^' {
-                    queue.push_back(lc);
-                }
-            } else {
-                *finished = finished
-                    .checked_add(t)
-                    .expect("Timeline count overflow. Use BigUint");
-            }
+            let t = std::mem::take(&mut active_timelines[p.x as usize]);
 
-            if self.in_bounds_col(right) {
-                let rc = right as usize;
-                self.active_timelines[rc] = self.active_timelines[rc]
-                    .checked_add(t)
-                    .expect("Timeline count overflow. Use BigUint");
+            for side in [Direction::West, Direction::East] {
+                let next = p.step(side);
+                if self.grid.contains(next) {
+                    active_timelines[next.x as usize] += &t;
 
-                if self.at(r, rc) == b'^' {
-                    queue.push_back(rc);
+                    if self.is_splitter(next) {
+                        queue.push_back(next);
+                    }
+                } else {
+                    *finished += &t;
                 }
-            } else {
-                *finished = finished
-                    .checked_add(t)
-                    .expect("Timeline count overflow. Use BigUint");
             }
         }
     }
@@ -203,14 +174,14 @@ mod tests {
     #[test]
     fn test_day_07_first_task() {
         let day_07 = Day07;
-        let solution = day_07.first(INPUT);
-        assert_eq!(Solution::new(21), solution);
+        let solution = day_07.parse(INPUT).and_then(|input| day_07.first(&input));
+        assert_eq!(Ok(Solution::new(21)), solution);
     }
 
     #[test]
     fn test_day_07_second_task() {
         let day_07 = Day07;
-        let solution = day_07.second(INPUT);
-        assert_eq!(Solution::new(40), solution);
+        let solution = day_07.parse(INPUT).and_then(|input| day_07.second(&input));
+        assert_eq!(Ok(Solution::new(40)), solution);
     }
 }
@@ -1,41 +1,32 @@
 use std::collections::HashMap;
 
-use crate::solvable::{Solution, Solvable};
+use crate::{
+    point::{Point, Point3D},
+    solvable::{Metadata, Solution, Solvable, SolveError},
+};
 
 pub struct Day08;
 
 impl Solvable for Day08 {
-    fn first(&self, input: &str) -> crate::solvable::Solution {
-        Solution::new(solve_first(input, 1000))
-    }
+    const METADATA: Metadata = Metadata {
+        year: 2025,
+        day: 8,
+        title: "Playground",
+        tags: &["graph", "union-find"],
+    };
 
-    fn second(&self, input: &str) -> crate::solvable::Solution {
-        Solution::new(solve_second(input))
-    }
-}
-
-#[derive(Debug, Clone, Copy)]
-struct Point3D {
-    x: i32,
-    y: i32,
-    z: i32,
-}
+    type Input = Playground;
 
-impl Point3D {
-    fn parse(line: &str) -> Self {
-        let mut it = line.split(',');
-        let x = it.next().unwrap().trim().parse::<i32>().unwrap();
-        let y = it.next().unwrap().trim().parse::<i32>().unwrap();
-        let z = it.next().unwrap().trim().parse::<i32>().unwrap();
+    fn parse(&self, input: &str) -> Result<Self::Input, SolveError> {
+        Playground::parse(input)
+    }
 
-        Self { x, y, z }
+    fn first(&self, playground: &Self::Input) -> Result<Solution, SolveError> {
+        Ok(Solution::new(playground.solve_first(1000)))
     }
 
-    fn dist_to(self, other: Self) -> i128 {
-        let dx = (self.x as i128) - (other.x as i128);
-        let dy = (self.y as i128) - (other.y as i128);
-        let dz = (self.z as i128) - (other.z as i128);
-        dx * dx + dy * dy + dz * dz
+    fn second(&self, playground: &Self::Input) -> Result<Solution, SolveError> {
+        Ok(Solution::new(playground.solve_second()?))
     }
 }
 
@@ -95,12 +86,19 @@ impl UnionFind {
     }
 }
 
-fn prepare_points(input: &str) -> Vec<Point3D> {
-    input
+fn prepare_points(input: &str) -> Result<Vec<Point3D>, SolveError> {
+    let points = input
         .lines()
-        .filter(|l| !l.trim().is_empty())
-        .map(Point3D::parse)
-        .collect()
+        .enumerate()
+        .filter(|(_, l)| !l.trim().is_empty())
+        .map(|(i, l)| Point3D::parse_at(i + 1, l))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if points.len() < 2 {
+        return Err(SolveError::new("at least two junction boxes are required"));
+    }
+
+    Ok(points)
 }
 
 fn build_sorted_edges(points: &[Point3D]) -> Vec<Edge> {
@@ -112,50 +110,60 @@ fn build_sorted_edges(points: &[Point3D]) -> Vec<Edge> {
             edges.push(Edge {
                 a: i,
                 b: j,
-                dist: points[i].dist_to(points[j]),
+                dist: points[i].distance_squared(points[j]),
             });
         }
     }
 
-    edges.sort_unstable_by(|e1, e2| (e1.dist, e1.a, e1.b).cmp(&(e2.dist, e2.a, e2.b)));
+    edges.sort_unstable_by_key(|e| (e.dist, e.a, e.b));
     edges
 }
 
-fn solve_first(input: &str, k: usize) -> i128 {
-    let points = prepare_points(input);
-    let edges = build_sorted_edges(&points);
-    let n = points.len();
+/// The junction boxes together with every pair of them, closest pair first.
+#[derive(Debug)]
+pub struct Playground {
+    points: Vec<Point3D>,
+    edges: Vec<Edge>,
+}
 
-    let mut uf = UnionFind::new(n);
+impl Playground {
+    fn parse(input: &str) -> Result<Self, SolveError> {
+        let points = prepare_points(input)?;
+        let edges = build_sorted_edges(&points);
 
-    for e in edges.into_iter().take(k) {
-        uf.union(e.a, e.b);
+        Ok(Self { points, edges })
     }
 
-    let mut sizes = uf.component_sizes();
-    sizes.sort_unstable_by(|a, b| b.cmp(a));
+    fn solve_first(&self, k: usize) -> i128 {
+        let mut uf = UnionFind::new(self.points.len());
 
-    sizes.into_iter().take(3).map(|x| x as i128).product()
-}
+        for e in self.edges.iter().take(k) {
+            uf.union(e.a, e.b);
+        }
 
-fn solve_second(input: &str) -> i128 {
-    let points = prepare_points(input);
-    let edges = build_sorted_edges(&points);
-    let n = points.len();
+        let mut sizes = uf.component_sizes();
+        sizes.sort_unstable_by(|a, b| b.cmp(a));
+
+        sizes.into_iter().take(3).map(|x| x as i128).product()
+    }
 
-    let mut uf = UnionFind::new(n);
-    let mut components = n;
+    fn solve_second(&self) -> Result<i128, SolveError> {
+        let mut uf = UnionFind::new(self.points.len());
+        let mut components = self.points.len();
 
-    for e in edges {
-        if uf.union(e.a, e.b) {
-            components -= 1;
-            if components == 1 {
-                return (points[e.a].x as i128) * (points[e.b].x as i128);
+        for e in &self.edges {
+            if uf.union(e.a, e.b) {
+                components -= 1;
+                if components == 1 {
+                    return Ok((self.points[e.a].x as i128) * (self.points[e.b].x as i128));
+                }
             }
         }
-    }
 
-    unreachable!("Complete graph should always become connected");
+        Err(SolveError::new(
+            "complete graph should always become connected",
+        ))
+    }
 }
 
 #[cfg(test)]
@@ -187,14 +195,14 @@ mod tests {
 
     #[test]
     fn test_day_08_first_task() {
-        let solution = Solution::new(solve_first(INPUT, 10));
-        assert_eq!(Solution::new(40), solution);
+        let solution = Playground::parse(INPUT).map(|p| Solution::new(p.solve_first(10)));
+        assert_eq!(Ok(Solution::new(40)), solution);
     }
 
     #[test]
     fn test_day_08_second_task() {
         let day_08 = Day08;
-        let solution = day_08.second(INPUT);
-        assert_eq!(Solution::new(25272), solution);
+        let solution = day_08.parse(INPUT).and_then(|input| day_08.second(&input));
+        assert_eq!(Ok(Solution::new(25272)), solution);
     }
 }
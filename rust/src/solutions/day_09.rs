@@ -1,49 +1,60 @@
 use crate::{
-    point::Point2D,
-    solvable::{Solution, Solvable},
+    point::{Point, Point2D, Rect},
+    polygon::Polygon,
+    solvable::{Metadata, Solution, Solvable, SolveError},
 };
 
 pub struct Day09;
 
 impl Solvable for Day09 {
-    fn first(&self, input: &str) -> crate::solvable::Solution {
-        let points = parse_points(input);
+    const METADATA: Metadata = Metadata {
+        year: 2025,
+        day: 9,
+        title: "Movie Theater",
+        tags: &["geometry"],
+    };
+
+    type Input = Vec<Point2D>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, SolveError> {
+        parse_points(input)
+    }
+
+    fn first(&self, points: &Self::Input) -> Result<Solution, SolveError> {
         let max_area = points.iter().enumerate().fold(0i128, |best, (i, p1)| {
             let best_with_p1 = points.iter().skip(i + 1).fold(0i128, |local_best, p2| {
-                let area = Rect(*p1, *p2).area();
+                let area = Rect::new(*p1, *p2).area();
                 local_best.max(area)
             });
             best.max(best_with_p1)
         });
 
-        Solution::new(max_area)
+        Ok(Solution::new(max_area))
     }
 
-    fn second(&self, input: &str) -> crate::solvable::Solution {
-        Solution::new(0)
-    }
-}
+    fn second(&self, points: &Self::Input) -> Result<Solution, SolveError> {
+        let floor = Polygon::new(points.clone())?.compress();
 
-struct Rect(Point2D, Point2D);
+        let max_area = points.iter().enumerate().fold(0i128, |best, (i, p1)| {
+            let best_with_p1 = points
+                .iter()
+                .skip(i + 1)
+                .map(|p2| Rect::new(*p1, *p2))
+                .filter(|rect| floor.contains_rect(rect))
+                .fold(0i128, |local_best, rect| local_best.max(rect.area()));
+            best.max(best_with_p1)
+        });
 
-impl Rect {
-    fn area(&self) -> i128 {
-        let dx = (self.0.x - self.1.x).abs() as i128;
-        let dy = (self.0.y - self.1.y).abs() as i128;
-        (dx + 1) * (dy + 1)
+        Ok(Solution::new(max_area))
     }
 }
 
-fn parse_points(input: &str) -> Vec<Point2D> {
+fn parse_points(input: &str) -> Result<Vec<Point2D>, SolveError> {
     input
         .lines()
-        .map(|line| {
-            let (x, y) = line.split_once(',').unwrap();
-            Point2D {
-                x: x.parse().unwrap(),
-                y: y.parse().unwrap(),
-            }
-        })
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| Point2D::parse_at(i + 1, line))
         .collect()
 }
 
@@ -65,14 +76,29 @@ mod tests {
     #[test]
     fn test_day_09_first_task() {
         let day_09 = Day09;
-        let solution = day_09.first(INPUT);
-        assert_eq!(Solution::new(50), solution);
+        let solution = day_09.parse(INPUT).and_then(|input| day_09.first(&input));
+        assert_eq!(Ok(Solution::new(50)), solution);
     }
 
     #[test]
     fn test_day_09_second_task() {
         let day_09 = Day09;
-        let solution = day_09.second(INPUT);
-        assert_eq!(Solution::new(0), solution);
+        let solution = day_09.parse(INPUT).and_then(|input| day_09.second(&input));
+        assert_eq!(Ok(Solution::new(24)), solution);
+    }
+
+    #[test]
+    fn test_day_09_second_task_adjacent_coordinates() {
+        let day_09 = Day09;
+        let input = "0,0\n3,0\n3,3\n2,3\n2,1\n1,1\n1,3\n0,3\n";
+        let solution = day_09.parse(input).and_then(|input| day_09.second(&input));
+        assert_eq!(Ok(Solution::new(16)), solution);
+    }
+
+    #[test]
+    fn test_day_09_trailing_blank_line() {
+        let day_09 = Day09;
+        let points = day_09.parse(&format!("{INPUT}\n\n"));
+        assert_eq!(8, points.map(|p| p.len()).unwrap_or_default());
     }
 }
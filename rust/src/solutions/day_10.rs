@@ -0,0 +1,485 @@
+use std::str::FromStr;
+
+use crate::solvable::{Metadata, Solution, Solvable, SolveError, column_of, parse_token};
+
+pub struct Day10;
+
+/// How many buttons may stay undetermined after elimination, as every
+/// combination of them is tried.
+const MAX_FREE_BUTTONS: usize = 24;
+
+/// How many combinations of free button presses the joltage search may try.
+const MAX_JOLTAGE_CANDIDATES: i128 = 100_000_000;
+
+impl Solvable for Day10 {
+    const METADATA: Metadata = Metadata {
+        year: 2025,
+        day: 10,
+        title: "Factory",
+        tags: &["linear-algebra"],
+    };
+
+    type Input = Vec<(usize, Machine)>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, SolveError> {
+        parse_machines(input)
+    }
+
+    fn first(&self, machines: &Self::Input) -> Result<Solution, SolveError> {
+        let solution = machines
+            .iter()
+            .map(|(line_no, m)| {
+                m.min_presses_for_lights()
+                    .map_err(|message| SolveError::at_line(*line_no, message))?
+                    .map(i128::from)
+                    .ok_or_else(|| SolveError::at_line(*line_no, "light pattern is unreachable"))
+            })
+            .sum::<Result<_, _>>()?;
+
+        Ok(Solution::new(solution))
+    }
+
+    fn second(&self, machines: &Self::Input) -> Result<Solution, SolveError> {
+        let solution = machines
+            .iter()
+            .map(|(line_no, m)| {
+                m.min_presses_for_joltage()
+                    .map_err(|message| SolveError::at_line(*line_no, message))?
+                    .ok_or_else(|| {
+                        SolveError::at_line(*line_no, "joltage requirements are unreachable")
+                    })
+            })
+            .sum::<Result<_, _>>()?;
+
+        Ok(Solution::new(solution))
+    }
+}
+
+/// The light pattern, button wiring and joltage requirements of a machine.
+#[derive(Debug)]
+pub struct Machine {
+    lights: u32,
+    buttons: Vec<Vec<usize>>,
+    joltage: Vec<i128>,
+}
+
+impl Machine {
+    fn parse(line_no: usize, line: &str) -> Result<Self, SolveError> {
+        let mut light_count = 0;
+        let mut lights = 0u32;
+        let mut buttons = Vec::new();
+        let mut joltage = Vec::new();
+
+        for token in line.split_whitespace() {
+            let column = column_of(line, token);
+            let error = |message: String| SolveError::at(line_no, column, message);
+
+            let mut chars = token.chars();
+            let (Some(open), Some(close)) = (chars.next(), chars.next_back()) else {
+                return Err(error(format!("unexpected token '{token}'")));
+            };
+            let inner = chars.as_str();
+
+            match (open, close) {
+                ('[', ']') => {
+                    if inner.len() > 32 || inner.bytes().any(|b| b != b'#' && b != b'.') {
+                        return Err(error(format!("invalid light pattern '{token}'")));
+                    }
+                    light_count = inner.len();
+                    lights = inner
+                        .bytes()
+                        .enumerate()
+                        .filter(|&(_, b)| b == b'#')
+                        .fold(0, |mask, (i, _)| mask | (1 << i));
+                }
+                ('(', ')') => {
+                    let mut wiring: Vec<usize> = parse_list(line_no, line, inner)?;
+                    if wiring.iter().any(|&i| i >= light_count) {
+                        return Err(error(format!(
+                            "button '{token}' is wired to a missing light"
+                        )));
+                    }
+                    // Buttons with the same wiring are interchangeable in both
+                    // parts, so only one of them is kept.
+                    wiring.sort_unstable();
+                    wiring.dedup();
+                    if !buttons.contains(&wiring) {
+                        buttons.push(wiring);
+                    }
+                }
+                ('{', '}') => joltage = parse_list(line_no, line, inner)?,
+                _ => return Err(error(format!("unexpected token '{token}'"))),
+            }
+        }
+
+        if joltage.len() != light_count {
+            return Err(SolveError::at_line(
+                line_no,
+                format!(
+                    "expected {light_count} joltage values, found {}",
+                    joltage.len()
+                ),
+            ));
+        }
+
+        Ok(Self {
+            lights,
+            buttons,
+            joltage,
+        })
+    }
+
+    /// Pressing a button twice cancels out, so every button is pressed at most
+    /// once and the lights are the XOR of the chosen buttons over GF(2). After
+    /// Gaussian elimination only the free buttons are enumerated, since they
+    /// determine all others.
+    fn min_presses_for_lights(&self) -> Result<Option<u32>, String> {
+        if self.buttons.len() > 128 {
+            return Err(format!(
+                "at most 128 buttons are supported, found {}",
+                self.buttons.len()
+            ));
+        }
+
+        // One equation per light: the buttons wired to it, and whether it is on.
+        let mut rows: Vec<(u128, bool)> = (0..self.joltage.len())
+            .map(|light| {
+                let coefs = self
+                    .buttons
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, wiring)| wiring.contains(&light))
+                    .fold(0u128, |bits, (j, _)| bits | (1 << j));
+                (coefs, self.lights & (1 << light) != 0)
+            })
+            .collect();
+
+        let mut pivots = Vec::new();
+        let mut free = Vec::new();
+        for col in 0..self.buttons.len() {
+            let bit = 1u128 << col;
+            let r = pivots.len();
+            let Some(p) = (r..rows.len()).find(|&i| rows[i].0 & bit != 0) else {
+                free.push(col);
+                continue;
+            };
+            rows.swap(r, p);
+            let pivot = rows[r];
+            for (i, row) in rows.iter_mut().enumerate() {
+                if i != r && row.0 & bit != 0 {
+                    *row = (row.0 ^ pivot.0, row.1 ^ pivot.1);
+                }
+            }
+            pivots.push(col);
+        }
+
+        if rows[pivots.len()..].iter().any(|&(_, on)| on) {
+            return Ok(None);
+        }
+        if free.len() > MAX_FREE_BUTTONS {
+            return Err(format!(
+                "{} buttons can be pressed freely, at most {MAX_FREE_BUTTONS} are supported",
+                free.len()
+            ));
+        }
+
+        let presses = (0u32..1 << free.len()).map(|chosen| {
+            let free_bits = free
+                .iter()
+                .enumerate()
+                .filter(|&(i, _)| chosen & (1 << i) != 0)
+                .fold(0u128, |bits, (_, &col)| bits | (1 << col));
+            let pivot_presses = rows
+                .iter()
+                .zip(&pivots)
+                .filter(|&(&(coefs, on), _)| on ^ ((coefs & free_bits).count_ones() % 2 == 1))
+                .count();
+            chosen.count_ones() + pivot_presses as u32
+        });
+
+        Ok(presses.min())
+    }
+
+    /// Solves `A x = b` for non-negative integer `x` minimising `sum(x)`, where
+    /// column `j` of `A` is the wiring of button `j` and `b` is the joltage.
+    fn min_presses_for_joltage(&self) -> Result<Option<i128>, String> {
+        let system = LinearSystem::new(&self.buttons, &self.joltage);
+        let bounds: Vec<i128> = self
+            .buttons
+            .iter()
+            .map(|wiring| wiring.iter().map(|&i| self.joltage[i]).min().unwrap_or(0))
+            .collect();
+
+        if !system.is_consistent() {
+            return Ok(None);
+        }
+
+        let candidates = system
+            .free
+            .iter()
+            .try_fold(1i128, |n, &f| n.checked_mul(bounds[f] + 1))
+            .filter(|&n| n <= MAX_JOLTAGE_CANDIDATES);
+        if candidates.is_none() {
+            return Err(format!(
+                "{} buttons can be pressed freely, too many to search",
+                system.free.len()
+            ));
+        }
+
+        let mut free_values = vec![0i128; system.free.len()];
+        let mut best = None;
+        system.search(&bounds, 0, &mut free_values, &mut best);
+
+        Ok(best)
+    }
+}
+
+/// Augmented matrix in reduced row echelon form with integer entries. Each
+/// pivot row reads `pivot * x[col] + sum(coef * x[free]) = rhs`.
+#[derive(Debug)]
+struct LinearSystem {
+    rows: Vec<Vec<i128>>,
+    pivots: Vec<usize>,
+    free: Vec<usize>,
+}
+
+impl LinearSystem {
+    fn new(buttons: &[Vec<usize>], joltage: &[i128]) -> Self {
+        let vars = buttons.len();
+        let mut rows: Vec<Vec<i128>> = joltage
+            .iter()
+            .enumerate()
+            .map(|(counter, &target)| {
+                let mut row: Vec<i128> = buttons
+                    .iter()
+                    .map(|wiring| i128::from(wiring.contains(&counter)))
+                    .collect();
+                row.push(target);
+                row
+            })
+            .collect();
+
+        let mut pivots = Vec::new();
+        let mut free = Vec::new();
+        let mut r = 0;
+
+        for col in 0..vars {
+            let Some(p) = (r..rows.len()).find(|&i| rows[i][col] != 0) else {
+                free.push(col);
+                continue;
+            };
+            rows.swap(r, p);
+            let pivot_row = rows[r].clone();
+            let a = pivot_row[col];
+
+            for (i, row) in rows.iter_mut().enumerate() {
+                let b = row[col];
+                if i == r || b == 0 {
+                    continue;
+                }
+                row.iter_mut()
+                    .zip(&pivot_row)
+                    .for_each(|(v, &pv)| *v = *v * a - pv * b);
+                let g = row.iter().fold(0, |g, &v| gcd(g, v));
+                if g > 1 {
+                    row.iter_mut().for_each(|v| *v /= g);
+                }
+            }
+
+            pivots.push(col);
+            r += 1;
+        }
+
+        Self { rows, pivots, free }
+    }
+
+    fn is_consistent(&self) -> bool {
+        self.rows
+            .iter()
+            .skip(self.pivots.len())
+            .all(|row| row.last() == Some(&0))
+    }
+
+    fn search(
+        &self,
+        bounds: &[i128],
+        idx: usize,
+        free_values: &mut [i128],
+        best: &mut Option<i128>,
+    ) {
+        if idx == self.free.len() {
+            if let Some(total) = self.evaluate(free_values)
+                && best.is_none_or(|b| total < b)
+            {
+                *best = Some(total);
+            }
+            return;
+        }
+
+        for v in 0..=bounds[self.free[idx]] {
+            free_values[idx] = v;
+            self.search(bounds, idx + 1, free_values, best);
+        }
+    }
+
+    fn evaluate(&self, free_values: &[i128]) -> Option<i128> {
+        let vars = self.pivots.len() + self.free.len();
+        let mut total: i128 = free_values.iter().sum();
+
+        for (row, &col) in self.rows.iter().zip(&self.pivots) {
+            let rest: i128 = self
+                .free
+                .iter()
+                .zip(free_values)
+                .map(|(&f, &v)| row[f] * v)
+                .sum();
+            let num = row[vars] - rest;
+            let den = row[col];
+
+            if num % den != 0 {
+                return None;
+            }
+            let x = num / den;
+            if x < 0 {
+                return None;
+            }
+            total += x;
+        }
+
+        Some(total)
+    }
+}
+
+fn gcd(a: i128, b: i128) -> i128 {
+    if b == 0 { a.abs() } else { gcd(b, a % b) }
+}
+
+fn parse_list<T: FromStr>(line_no: usize, line: &str, list: &str) -> Result<Vec<T>, SolveError> {
+    list.split(',')
+        .map(|s| parse_token(line_no, line, s))
+        .collect()
+}
+
+fn parse_machines(input: &str) -> Result<Vec<(usize, Machine)>, SolveError> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, l)| !l.trim().is_empty())
+        .map(|(i, l)| Ok((i + 1, Machine::parse(i + 1, l)?)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::solvable::Solution;
+
+    use super::*;
+
+    const INPUT: &str = "[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}
+[...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}
+[.###.#] (0,1,2,3,4) (0,3,4) (0,1,2,4,5) (1,2) {10,11,11,5,10,5}";
+
+    #[test]
+    fn test_day_10_first_task() {
+        let day_10 = Day10;
+        let solution = day_10.parse(INPUT).and_then(|input| day_10.first(&input));
+        assert_eq!(Ok(Solution::new(7)), solution);
+    }
+
+    /// A machine with six lights and a button for every non-empty set of
+    /// them, which leaves 57 buttons free after elimination.
+    fn every_wiring() -> String {
+        let buttons: Vec<String> = (1..64u32)
+            .map(|set| {
+                let lights: Vec<String> = (0..6)
+                    .filter(|i| set & (1 << i) != 0)
+                    .map(|i| i.to_string())
+                    .collect();
+                format!("({})", lights.join(","))
+            })
+            .collect();
+        format!("[#.#.#.] {} {{1,1,1,1,1,1}}", buttons.join(" "))
+    }
+
+    #[test]
+    fn test_day_10_duplicate_buttons_first_task() {
+        let buttons = ["(0)"; 33].join(" ");
+        let day_10 = Day10;
+        let solution = day_10
+            .parse(&format!("[#] {buttons} {{1}}"))
+            .and_then(|input| day_10.first(&input));
+        assert_eq!(Ok(Solution::new(1)), solution);
+    }
+
+    #[test]
+    fn test_day_10_duplicate_buttons_second_task() {
+        let buttons = ["(0)"; 33].join(" ");
+        let day_10 = Day10;
+        let solution = day_10
+            .parse(&format!("[#] {buttons} {{1}}"))
+            .and_then(|input| day_10.second(&input));
+        assert_eq!(Ok(Solution::new(1)), solution);
+    }
+
+    #[test]
+    fn test_day_10_duplicate_wiring_in_any_order() {
+        let day_10 = Day10;
+        let machines = day_10.parse("[##] (0) (0,1) (1,0) (1,1,0) {1,1}").unwrap();
+        assert_eq!(vec![vec![0], vec![0, 1]], machines[0].1.buttons);
+    }
+
+    #[test]
+    fn test_day_10_redundant_buttons_first_task() {
+        let buttons = ["(0,1)"; 12].join(" ");
+        let day_10 = Day10;
+        let solution = day_10
+            .parse(&format!("[##] (0) {buttons} {{1,1}}"))
+            .and_then(|input| day_10.first(&input));
+        assert_eq!(Ok(Solution::new(1)), solution);
+    }
+
+    #[test]
+    fn test_day_10_too_many_free_buttons_first_task() {
+        let day_10 = Day10;
+        let solution = day_10
+            .parse(&every_wiring())
+            .and_then(|input| day_10.first(&input));
+        assert_eq!(
+            Err(SolveError::at_line(
+                1,
+                "57 buttons can be pressed freely, at most 24 are supported"
+            )),
+            solution
+        );
+    }
+
+    #[test]
+    fn test_day_10_too_many_free_buttons_second_task() {
+        let day_10 = Day10;
+        let solution = day_10
+            .parse(&every_wiring())
+            .and_then(|input| day_10.second(&input));
+        assert_eq!(
+            Err(SolveError::at_line(
+                1,
+                "57 buttons can be pressed freely, too many to search"
+            )),
+            solution
+        );
+    }
+
+    #[test]
+    fn test_day_10_rejects_unexpected_tokens() {
+        assert_eq!(
+            Err(SolveError::at(1, 5, "unexpected token 'é'")),
+            Day10.parse("[#] é {1}").map(|_| ())
+        );
+    }
+
+    #[test]
+    fn test_day_10_second_task() {
+        let day_10 = Day10;
+        let solution = day_10.parse(INPUT).and_then(|input| day_10.second(&input));
+        assert_eq!(Ok(Solution::new(33)), solution);
+    }
+}
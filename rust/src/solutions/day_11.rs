@@ -0,0 +1,276 @@
+use std::{collections::HashMap, fmt::Display};
+
+use num_bigint::BigUint;
+
+use crate::solvable::{Metadata, Solution, Solvable, SolveError};
+
+pub struct Day11;
+
+impl Solvable for Day11 {
+    const METADATA: Metadata = Metadata {
+        year: 2025,
+        day: 11,
+        title: "Reactor",
+        tags: &["graph"],
+    };
+
+    type Input = Graph;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, SolveError> {
+        Graph::parse(input)
+    }
+
+    fn first(&self, graph: &Self::Input) -> Result<Solution, SolveError> {
+        let solution = graph.count_paths("you", "out")?;
+
+        Ok(Solution::from(solution))
+    }
+
+    fn second(&self, graph: &Self::Input) -> Result<Solution, SolveError> {
+        let solution = graph.count_paths_through("svr", "out", &["dac", "fft"])?;
+
+        Ok(Solution::from(solution))
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct CycleError {
+    node: String,
+}
+
+impl Display for CycleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "device graph contains a cycle through '{}', path count is unbounded",
+            self.node
+        )
+    }
+}
+
+impl From<CycleError> for SolveError {
+    fn from(e: CycleError) -> Self {
+        SolveError::new(e.to_string())
+    }
+}
+
+/// Path counts grow exponentially with the depth of the graph, so they are
+/// kept as big integers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Visit {
+    InProgress,
+    Done(BigUint),
+}
+
+/// The devices and the outputs each of them is connected to.
+#[derive(Debug)]
+pub struct Graph {
+    ids: HashMap<String, usize>,
+    names: Vec<String>,
+    children: Vec<Vec<usize>>,
+}
+
+impl Graph {
+    fn parse(input: &str) -> Result<Self, SolveError> {
+        let mut graph = Self {
+            ids: HashMap::new(),
+            names: Vec::new(),
+            children: Vec::new(),
+        };
+
+        for (i, line) in input.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let (name, outputs) = line.split_once(':').ok_or_else(|| {
+                SolveError::at_line(i + 1, format!("expected 'name: outputs', found '{line}'"))
+            })?;
+            let from = graph.intern(name.trim());
+
+            for child in outputs.split_whitespace() {
+                let to = graph.intern(child);
+                graph.children[from].push(to);
+            }
+        }
+
+        Ok(graph)
+    }
+
+    fn intern(&mut self, name: &str) -> usize {
+        if let Some(&id) = self.ids.get(name) {
+            return id;
+        }
+
+        let id = self.names.len();
+        self.ids.insert(name.to_string(), id);
+        self.names.push(name.to_string());
+        self.children.push(Vec::new());
+        id
+    }
+
+    fn count_paths(&self, from: &str, to: &str) -> Result<BigUint, CycleError> {
+        let (Some(&from), Some(&to)) = (self.ids.get(from), self.ids.get(to)) else {
+            return Ok(BigUint::ZERO);
+        };
+
+        let mut memo = vec![None; self.names.len()];
+        self.count_from(from, to, &mut memo)
+    }
+
+    /// Counts paths that visit every node in `required`, in any order. In a
+    /// DAG the segments between consecutive required nodes can never share a
+    /// node, so each ordering contributes the product of its segment counts.
+    fn count_paths_through(
+        &self,
+        from: &str,
+        to: &str,
+        required: &[&str],
+    ) -> Result<BigUint, CycleError> {
+        let mut total = BigUint::ZERO;
+
+        for order in permutations(required) {
+            let stops: Vec<&str> = std::iter::once(from)
+                .chain(order)
+                .chain(std::iter::once(to))
+                .collect();
+
+            let mut product = BigUint::from(1u8);
+            for leg in stops.windows(2) {
+                product *= self.count_paths(leg[0], leg[1])?;
+                if product == BigUint::ZERO {
+                    break;
+                }
+            }
+            total += product;
+        }
+
+        Ok(total)
+    }
+
+    fn count_from(
+        &self,
+        node: usize,
+        target: usize,
+        memo: &mut [Option<Visit>],
+    ) -> Result<BigUint, CycleError> {
+        if node == target {
+            return Ok(BigUint::from(1u8));
+        }
+
+        match &memo[node] {
+            Some(Visit::Done(count)) => return Ok(count.clone()),
+            Some(Visit::InProgress) => {
+                return Err(CycleError {
+                    node: self.names[node].clone(),
+                });
+            }
+            None => {}
+        }
+
+        memo[node] = Some(Visit::InProgress);
+
+        let mut count = BigUint::ZERO;
+        for &child in &self.children[node] {
+            count += self.count_from(child, target, memo)?;
+        }
+
+        memo[node] = Some(Visit::Done(count.clone()));
+        Ok(count)
+    }
+}
+
+fn permutations<'a>(items: &[&'a str]) -> Vec<Vec<&'a str>> {
+    if items.is_empty() {
+        return vec![vec![]];
+    }
+
+    (0..items.len())
+        .flat_map(|i| {
+            let mut rest = items.to_vec();
+            let head = rest.remove(i);
+            permutations(&rest).into_iter().map(move |mut tail| {
+                tail.insert(0, head);
+                tail
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::solvable::Solution;
+
+    use super::*;
+
+    const INPUT: &str = "aaa: you hhh
+you: bbb ccc
+bbb: ddd eee
+ccc: ddd eee fff
+ddd: ggg
+eee: out
+fff: out
+ggg: out
+hhh: ccc fff iii
+iii: out";
+
+    const INPUT_SECOND: &str = "svr: aaa bbb
+aaa: fft
+fft: ccc
+bbb: tty
+tty: ccc
+ccc: ddd eee
+ddd: hub
+hub: fff
+eee: dac
+dac: fff
+fff: ggg hhh
+ggg: out
+hhh: out";
+
+    #[test]
+    fn test_day_11_first_task() {
+        let day_11 = Day11;
+        let solution = day_11.parse(INPUT).and_then(|input| day_11.first(&input));
+        assert_eq!(Ok(Solution::new(5)), solution);
+    }
+
+    #[test]
+    fn test_day_11_second_task() {
+        let day_11 = Day11;
+        let solution = day_11
+            .parse(INPUT_SECOND)
+            .and_then(|input| day_11.second(&input));
+        assert_eq!(Ok(Solution::new(2)), solution);
+    }
+
+    #[test]
+    fn test_day_11_counts_beyond_i128() {
+        // Every layer doubles the number of paths: 2^130 in total.
+        let layers = 130;
+        let mut input = String::from("you: a0 b0\n");
+        for i in 0..layers {
+            let next = if i + 1 == layers {
+                "out out".to_string()
+            } else {
+                format!("a{} b{}", i + 1, i + 1)
+            };
+            input.push_str(&format!("a{i}: m{i}\nb{i}: m{i}\nm{i}: {next}\n"));
+        }
+
+        let day_11 = Day11;
+        let solution = day_11.parse(&input).and_then(|input| day_11.first(&input));
+        assert_eq!(Ok(Solution::from(BigUint::from(1u8) << (layers + 1))), solution);
+    }
+
+    #[test]
+    fn test_day_11_cycle_is_reported() {
+        let graph = Graph::parse("you: aaa\naaa: bbb\nbbb: aaa out").unwrap();
+        assert_eq!(
+            Err(CycleError {
+                node: "aaa".to_string()
+            }),
+            graph.count_paths("you", "out")
+        );
+    }
+}
@@ -0,0 +1,399 @@
+use std::collections::HashSet;
+
+use crate::{
+    point::{Point2D, Rect},
+    polyomino::Polyomino,
+    solvable::{Metadata, Solution, Solvable, SolveError, parse_token},
+};
+
+pub struct Day12;
+
+impl Solvable for Day12 {
+    const METADATA: Metadata = Metadata {
+        year: 2025,
+        day: 12,
+        title: "Christmas Tree Farm",
+        tags: &["packing"],
+    };
+
+    type Input = (Vec<Polyomino>, Vec<Region>);
+
+    fn parse(&self, input: &str) -> Result<Self::Input, SolveError> {
+        parse(input)
+    }
+
+    fn first(&self, (shapes, regions): &Self::Input) -> Result<Solution, SolveError> {
+        let packer = Packer::new(shapes);
+
+        let solution = regions.iter().filter(|r| packer.fits(r)).count() as i128;
+
+        Ok(Solution::new(solution))
+    }
+
+    fn second(&self, _input: &Self::Input) -> Result<Solution, SolveError> {
+        // The final day only has a single puzzle.
+        Ok(Solution::Unsolved)
+    }
+}
+
+/// A region under a tree and how many presents of each shape it must hold.
+#[derive(Debug)]
+pub struct Region {
+    width: usize,
+    height: usize,
+    counts: Vec<usize>,
+}
+
+impl Region {
+    fn parse(line_no: usize, line: &str, shapes: usize) -> Result<Self, SolveError> {
+        let malformed =
+            || SolveError::at_line(line_no, format!("expected 'WxH: counts', found '{line}'"));
+        let (size, counts) = line.split_once(':').ok_or_else(malformed)?;
+        let (w, h) = size.split_once('x').ok_or_else(malformed)?;
+
+        let counts = counts
+            .split_whitespace()
+            .map(|c| parse_token(line_no, line, c))
+            .collect::<Result<Vec<_>, _>>()?;
+        if counts.len() != shapes {
+            return Err(SolveError::at_line(
+                line_no,
+                format!("expected {shapes} shape counts, found {}", counts.len()),
+            ));
+        }
+
+        Ok(Self {
+            width: parse_token(line_no, line, w)?,
+            height: parse_token(line_no, line, h)?,
+            counts,
+        })
+    }
+}
+
+fn parse(input: &str) -> Result<(Vec<Polyomino>, Vec<Region>), SolveError> {
+    let mut shapes = Vec::new();
+    let mut regions = Vec::new();
+    let mut lines = input.lines().enumerate().peekable();
+
+    while let Some((i, line)) = lines.next() {
+        let line_no = i + 1;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        if line.contains('x') {
+            regions.push(Region::parse(line_no, line, shapes.len())?);
+            continue;
+        }
+
+        let idx: usize = parse_token(line_no, line, line.trim().trim_end_matches(':'))?;
+        if idx != shapes.len() {
+            return Err(SolveError::at_line(
+                line_no,
+                format!("expected shape {}, found shape {idx}", shapes.len()),
+            ));
+        }
+
+        let mut rows = String::new();
+        while let Some((_, row)) = lines.next_if(|(_, l)| !l.trim().is_empty()) {
+            rows.push_str(row);
+            rows.push('\n');
+        }
+
+        let shape = Polyomino::parse(&rows, b'#');
+        if shape.is_empty() {
+            return Err(SolveError::at_line(
+                line_no,
+                format!("shape {idx} has no cells"),
+            ));
+        }
+        shapes.push(shape);
+    }
+
+    Ok((shapes, regions))
+}
+
+/// A placement of one orientation, with offsets relative to its first cell in
+/// reading order so it can be anchored on the first empty cell of the region.
+#[derive(Debug)]
+struct Placement {
+    offsets: Vec<Point2D>,
+}
+
+impl Placement {
+    fn new(shape: &Polyomino) -> Self {
+        let anchor = shape.cells()[0];
+        let offsets = shape
+            .cells()
+            .iter()
+            .map(|&p| p - anchor)
+            .collect();
+
+        Self { offsets }
+    }
+}
+
+#[derive(Debug)]
+struct Packer {
+    areas: Vec<usize>,
+    block_w: usize,
+    block_h: usize,
+    placements: Vec<Vec<Placement>>,
+}
+
+impl Packer {
+    fn new(shapes: &[Polyomino]) -> Self {
+        Self {
+            areas: shapes.iter().map(Polyomino::len).collect(),
+            block_w: shapes
+                .iter()
+                .filter_map(Polyomino::bounds)
+                .map(|b| b.width() as usize)
+                .max()
+                .unwrap_or(0),
+            block_h: shapes
+                .iter()
+                .filter_map(Polyomino::bounds)
+                .map(|b| b.height() as usize)
+                .max()
+                .unwrap_or(0),
+            placements: shapes
+                .iter()
+                .map(|s| s.orientations().iter().map(Placement::new).collect())
+                .collect(),
+        }
+    }
+
+    /// How far past its anchor cell, in row-major order, any placement can
+    /// extend within a region of the given width.
+    fn reach(&self, width: usize) -> usize {
+        self.placements
+            .iter()
+            .flatten()
+            .flat_map(|p| &p.offsets)
+            .map(|o| (o.y as usize * width).saturating_add_signed(o.x as isize) + 1)
+            .max()
+            .unwrap_or(1)
+    }
+
+    fn fits(&self, region: &Region) -> bool {
+        let cells = region.width * region.height;
+        let needed: usize = region
+            .counts
+            .iter()
+            .zip(&self.areas)
+            .map(|(c, a)| c * a)
+            .sum();
+        if needed > cells {
+            return false;
+        }
+
+        let pieces: usize = region.counts.iter().sum();
+        if pieces == 0 {
+            return true;
+        }
+
+        let blocks = (region.width / self.block_w) * (region.height / self.block_h);
+        if pieces <= blocks {
+            return true;
+        }
+
+        // Orientations cover transposes, so scanning along the shorter side is
+        // equivalent and keeps the memoized window small.
+        let width = region.width.min(region.height);
+        let height = region.width.max(region.height);
+
+        let mut search = Search {
+            packer: self,
+            width,
+            bounds: Rect::new(
+                Point2D::ORIGIN,
+                Point2D::new(width as i32 - 1, height as i32 - 1),
+            ),
+            filled: vec![false; cells],
+            remaining: region.counts.clone(),
+            pieces_left: pieces,
+            reach: self.reach(width),
+            failed: HashSet::new(),
+        };
+        search.solve(0, cells - needed)
+    }
+}
+
+/// Backtracking exact cover over the region's cells: every cell is either
+/// covered by the anchor of some placement or deliberately left empty, which
+/// spends one unit of the slack between the region and the pieces' area.
+///
+/// Cells before the anchor are all decided and no placement reaches more than
+/// `reach` cells past it, so a dead end is fully described by the anchor, the
+/// filled cells in that window and the remaining pieces, and is memoized.
+struct Search<'a> {
+    packer: &'a Packer,
+    width: usize,
+    bounds: Rect,
+    filled: Vec<bool>,
+    remaining: Vec<usize>,
+    pieces_left: usize,
+    reach: usize,
+    failed: HashSet<(usize, u128, Vec<usize>)>,
+}
+
+impl Search<'_> {
+    fn solve(&mut self, from: usize, slack: usize) -> bool {
+        if self.pieces_left == 0 {
+            return true;
+        }
+
+        let Some(pos) = (from..self.filled.len()).find(|&i| !self.filled[i]) else {
+            return false;
+        };
+
+        let key = self.state_key(pos);
+        if key.as_ref().is_some_and(|k| self.failed.contains(k)) {
+            return false;
+        }
+
+        if self.try_cover(pos, slack) {
+            return true;
+        }
+
+        if let Some(key) = key {
+            self.failed.insert(key);
+        }
+
+        false
+    }
+
+    fn state_key(&self, pos: usize) -> Option<(usize, u128, Vec<usize>)> {
+        if self.reach > 128 {
+            return None;
+        }
+
+        let window = self.filled[pos..(pos + self.reach).min(self.filled.len())]
+            .iter()
+            .enumerate()
+            .fold(0u128, |bits, (i, &f)| bits | (u128::from(f) << i));
+
+        Some((pos, window, self.remaining.clone()))
+    }
+
+    fn try_cover(&mut self, pos: usize, slack: usize) -> bool {
+        for shape in 0..self.remaining.len() {
+            if self.remaining[shape] == 0 {
+                continue;
+            }
+
+            for placement in &self.packer.placements[shape] {
+                let Some(cells) = self.cells_for(placement, pos) else {
+                    continue;
+                };
+
+                self.set(&cells, true);
+                self.remaining[shape] -= 1;
+                self.pieces_left -= 1;
+
+                let found = self.solve(pos + 1, slack);
+
+                self.pieces_left += 1;
+                self.remaining[shape] += 1;
+                self.set(&cells, false);
+
+                if found {
+                    return true;
+                }
+            }
+        }
+
+        if slack > 0 {
+            self.filled[pos] = true;
+            let found = self.solve(pos + 1, slack - 1);
+            self.filled[pos] = false;
+            return found;
+        }
+
+        false
+    }
+
+    fn cells_for(&self, placement: &Placement, pos: usize) -> Option<Vec<usize>> {
+        let anchor = Point2D::new((pos % self.width) as i32, (pos / self.width) as i32);
+
+        placement
+            .offsets
+            .iter()
+            .map(|&o| {
+                let p = anchor + o;
+                if !self.bounds.contains(p) {
+                    return None;
+                }
+                let idx = p.y as usize * self.width + p.x as usize;
+                (!self.filled[idx]).then_some(idx)
+            })
+            .collect()
+    }
+
+    fn set(&mut self, cells: &[usize], value: bool) {
+        cells.iter().for_each(|&i| self.filled[i] = value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::solvable::Solution;
+
+    use super::*;
+
+    const INPUT: &str = "0:
+###
+##.
+##.
+
+1:
+###
+##.
+.##
+
+2:
+.##
+###
+##.
+
+3:
+##.
+###
+##.
+
+4:
+###
+#..
+###
+
+5:
+###
+.#.
+###
+
+4x4: 0 0 0 0 2 0
+12x5: 1 0 1 0 2 2
+12x5: 1 0 1 0 3 2";
+
+    #[test]
+    fn test_day_12_first_task() {
+        let day_12 = Day12;
+        let solution = day_12.parse(INPUT).and_then(|input| day_12.first(&input));
+        assert_eq!(Ok(Solution::new(2)), solution);
+    }
+
+    #[test]
+    fn test_day_12_regions_without_shapes() {
+        let day_12 = Day12;
+        let solution = day_12.parse("4x4:").and_then(|input| day_12.first(&input));
+        assert_eq!(Ok(Solution::new(1)), solution);
+    }
+
+    #[test]
+    fn test_day_12_orientations() {
+        let (shapes, _) = parse(INPUT).unwrap();
+        assert_eq!(8, shapes[0].orientations().len());
+        assert_eq!(2, shapes[5].orientations().len());
+    }
+}
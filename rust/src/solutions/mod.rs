@@ -1,19 +1,31 @@
-pub mod day_01;
-pub mod day_02;
-pub mod day_03;
-pub mod day_04;
-pub mod day_05;
-pub mod day_06;
-pub mod day_07;
-pub mod day_08;
-pub mod day_09;
+use crate::solvable::Registrations;
 
-pub use day_01::*;
-pub use day_02::*;
-pub use day_03::*;
-pub use day_04::*;
-pub use day_05::*;
-pub use day_06::*;
-pub use day_07::*;
-pub use day_08::*;
-pub use day_09::*;
+/// Declares the module of each day, which holds its solver and the type its
+/// input is parsed into, and registers the solver, so adding a day takes a
+/// single line in the list below.
+macro_rules! days {
+    ($($module:ident::$solver:ident),* $(,)?) => {
+        $(
+            pub mod $module;
+        )*
+
+        pub(crate) fn register(registrations: &mut Registrations) {
+            $(registrations.add($module::$solver);)*
+        }
+    };
+}
+
+days! {
+    day_01::Day01,
+    day_02::Day02,
+    day_03::Day03,
+    day_04::Day04,
+    day_05::Day05,
+    day_06::Day06,
+    day_07::Day07,
+    day_08::Day08,
+    day_09::Day09,
+    day_10::Day10,
+    day_11::Day11,
+    day_12::Day12,
+}
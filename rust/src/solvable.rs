@@ -1,52 +1,559 @@
-use std::fmt::Display;
+use std::{collections::BTreeMap, fmt::Display, str::FromStr};
 
-use crate::solutions::{Day01, Day02, Day03, Day04, Day05, Day06, Day07, Day08, Day09};
+use num_bigint::{BigInt, BigUint};
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct Solution(i128);
+use crate::{
+    inputs::{InputMode, normalize},
+    solutions,
+};
+
+#[derive(Debug, Clone)]
+pub enum Solution {
+    Number(i128),
+    /// An integer that does not fit into `i128`. Equal to a `Number` holding
+    /// the same value.
+    BigNumber(BigInt),
+    Text(String),
+    Multiple(Vec<Solution>),
+    /// The part has no answer to give, like the second half of the last day.
+    Unsolved,
+    NotImplemented,
+}
 
 impl Solution {
     pub fn new(num: i128) -> Self {
-        Self(num)
+        Self::Number(num)
+    }
+
+    /// Whether this is an actual answer rather than an unsolved or
+    /// not-implemented marker.
+    pub fn is_answer(&self) -> bool {
+        !matches!(self, Self::Unsolved | Self::NotImplemented)
+    }
+
+    /// The bare answer, without the surrounding sentence of `Display`.
+    pub fn value(&self) -> String {
+        match self {
+            Self::Number(n) => n.to_string(),
+            Self::BigNumber(n) => n.to_string(),
+            Self::Text(s) => s.clone(),
+            Self::Multiple(values) => values
+                .iter()
+                .map(Self::value)
+                .collect::<Vec<_>>()
+                .join(", "),
+            Self::Unsolved => "unsolved".to_string(),
+            Self::NotImplemented => "not implemented".to_string(),
+        }
+    }
+}
+
+impl PartialEq for Solution {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Number(a), Self::Number(b)) => a == b,
+            (Self::BigNumber(a), Self::BigNumber(b)) => a == b,
+            (Self::Number(a), Self::BigNumber(b)) | (Self::BigNumber(b), Self::Number(a)) => {
+                BigInt::from(*a) == *b
+            }
+            (Self::Text(a), Self::Text(b)) => a == b,
+            (Self::Multiple(a), Self::Multiple(b)) => a == b,
+            (Self::Unsolved, Self::Unsolved) | (Self::NotImplemented, Self::NotImplemented) => true,
+            _ => false,
+        }
     }
 }
 
+impl Eq for Solution {}
+
 impl Display for Solution {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "The solution is {}", self.0)
+        match self {
+            Self::Unsolved => write!(f, "There is no solution"),
+            Self::NotImplemented => write!(f, "Not implemented yet"),
+            _ => write!(f, "The solution is {}", self.value()),
+        }
+    }
+}
+
+macro_rules! impl_from_int {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Solution {
+            fn from(n: $t) -> Self {
+                Self::Number(i128::from(n))
+            }
+        })*
+    };
+}
+
+impl_from_int!(i8, i16, i32, i64, i128, u8, u16, u32, u64);
+
+impl From<usize> for Solution {
+    fn from(n: usize) -> Self {
+        Self::from(n as u64)
+    }
+}
+
+impl From<u128> for Solution {
+    fn from(n: u128) -> Self {
+        i128::try_from(n).map_or_else(|_| Self::BigNumber(BigInt::from(n)), Self::Number)
+    }
+}
+
+impl From<BigInt> for Solution {
+    fn from(n: BigInt) -> Self {
+        i128::try_from(&n).map_or(Self::BigNumber(n), Self::Number)
+    }
+}
+
+impl From<BigUint> for Solution {
+    fn from(n: BigUint) -> Self {
+        Self::from(BigInt::from(n))
+    }
+}
+
+impl From<String> for Solution {
+    fn from(s: String) -> Self {
+        Self::Text(s)
+    }
+}
+
+impl From<&str> for Solution {
+    fn from(s: &str) -> Self {
+        Self::Text(s.to_string())
+    }
+}
+
+impl<T: Into<Solution>> From<Vec<T>> for Solution {
+    fn from(values: Vec<T>) -> Self {
+        Self::Multiple(values.into_iter().map(Into::into).collect())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Part {
+    First,
+    Second,
+}
+
+impl Part {
+    pub const ALL: [Self; 2] = [Self::First, Self::Second];
+
+    pub fn number(self) -> u8 {
+        match self {
+            Self::First => 1,
+            Self::Second => 2,
+        }
+    }
+
+    pub fn from_number(n: u8) -> Option<Self> {
+        Self::ALL.into_iter().find(|p| p.number() == n)
+    }
+}
+
+/// A failure while parsing or solving a puzzle. Solvers fill in the location
+/// within the input, the registry attaches the day and part.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SolveError {
+    pub day: Option<usize>,
+    pub part: Option<Part>,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    pub message: String,
+}
+
+impl SolveError {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            day: None,
+            part: None,
+            line: None,
+            column: None,
+            message: message.into(),
+        }
+    }
+
+    /// An error at a 1-based line of the input.
+    pub fn at_line(line: usize, message: impl Into<String>) -> Self {
+        Self {
+            line: Some(line),
+            ..Self::new(message)
+        }
+    }
+
+    /// An error at a 1-based line and column of the input.
+    pub fn at(line: usize, column: usize, message: impl Into<String>) -> Self {
+        Self {
+            column: Some(column),
+            ..Self::at_line(line, message)
+        }
+    }
+
+    fn in_day(self, day: usize) -> Self {
+        Self {
+            day: Some(day),
+            ..self
+        }
+    }
+
+    fn in_part(self, day: usize, part: Part) -> Self {
+        Self {
+            day: Some(day),
+            part: Some(part),
+            ..self
+        }
+    }
+}
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(day) = self.day {
+            write!(f, "day {day} ")?;
+        }
+        if let Some(part) = self.part {
+            write!(f, "part {} ", part.number())?;
+        }
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => write!(f, "at line {line}, column {column} ")?,
+            (Some(line), None) => write!(f, "at line {line} ")?,
+            _ => {}
+        }
+        write!(f, "failed: {}", self.message)
+    }
+}
+
+/// The 1-based column of `token` within `line`, where `token` is a subslice of
+/// `line` as produced by `split`, `trim` and friends.
+pub(crate) fn column_of(line: &str, token: &str) -> usize {
+    (token.as_ptr() as usize)
+        .checked_sub(line.as_ptr() as usize)
+        .filter(|&offset| offset <= line.len())
+        .map_or(1, |offset| offset + 1)
+}
+
+/// Parses `token` found on a 1-based `line`, reporting its column on failure.
+pub(crate) fn parse_token<T: FromStr>(
+    line_no: usize,
+    line: &str,
+    token: &str,
+) -> Result<T, SolveError> {
+    token.trim().parse().map_err(|_| {
+        SolveError::at(
+            line_no,
+            column_of(line, token),
+            format!("invalid number '{}'", token.trim()),
+        )
+    })
+}
+
+/// Descriptive details of a puzzle, used to label and select solvers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Metadata {
+    pub year: u16,
+    pub day: usize,
+    pub title: &'static str,
+    /// Techniques or puzzle kinds, like "graph" or "grid".
+    pub tags: &'static [&'static str],
+}
+
+impl Metadata {
+    /// Whether the title contains `name`, ignoring case, or one of the tags
+    /// equals it.
+    pub fn matches(&self, name: &str) -> bool {
+        let name = name.to_lowercase();
+        self.title.to_lowercase().contains(&name) || self.tags.iter().any(|&t| t == name)
+    }
+}
+
+/// A puzzle solver for one day. The input is parsed once by `parse` and the
+/// parsed form is shared by both parts.
+pub trait Solvable: Send + Sync {
+    const METADATA: Metadata;
+
+    /// How the registry prepares the input before handing it to `parse`.
+    const INPUT_MODE: InputMode = InputMode::Normalized;
+
+    type Input: Send + Sync;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, SolveError>;
+
+    fn first(&self, input: &Self::Input) -> Result<Solution, SolveError>;
+
+    fn second(&self, _input: &Self::Input) -> Result<Solution, SolveError> {
+        Ok(Solution::NotImplemented)
     }
 }
 
-pub trait Solvable {
-    fn first(&self, input: &str) -> Solution;
-    fn second(&self, input: &str) -> Solution;
+/// A solver working directly on the input text, for days whose parts share
+/// no parsing. Every `RawSolvable` is a `Solvable` whose parsed input is the
+/// text itself.
+pub trait RawSolvable: Send + Sync {
+    const METADATA: Metadata;
+
+    const INPUT_MODE: InputMode = InputMode::Normalized;
+
+    fn first(&self, input: &str) -> Result<Solution, SolveError>;
+
+    fn second(&self, _input: &str) -> Result<Solution, SolveError> {
+        Ok(Solution::NotImplemented)
+    }
+}
+
+impl<T: RawSolvable> Solvable for T {
+    const METADATA: Metadata = <T as RawSolvable>::METADATA;
+    const INPUT_MODE: InputMode = <T as RawSolvable>::INPUT_MODE;
+
+    type Input = String;
+
+    fn parse(&self, input: &str) -> Result<String, SolveError> {
+        Ok(input.to_string())
+    }
+
+    fn first(&self, input: &String) -> Result<Solution, SolveError> {
+        RawSolvable::first(self, input)
+    }
+
+    fn second(&self, input: &String) -> Result<Solution, SolveError> {
+        RawSolvable::second(self, input)
+    }
+}
+
+/// Object-safe view of a `Solvable`, hiding its parsed input type.
+trait DynSolvable: Send + Sync {
+    fn metadata(&self) -> Metadata;
+
+    fn parse<'a>(&'a self, input: &str) -> Result<Box<dyn DynParsed + 'a>, SolveError>;
+}
+
+trait DynParsed: Send + Sync {
+    fn solve(&self, part: Part) -> Result<Solution, SolveError>;
+}
+
+struct WithSolver<'a, S: Solvable> {
+    solver: &'a S,
+    input: S::Input,
+}
+
+impl<S: Solvable> DynSolvable for S {
+    fn metadata(&self) -> Metadata {
+        S::METADATA
+    }
+
+    fn parse<'a>(&'a self, input: &str) -> Result<Box<dyn DynParsed + 'a>, SolveError> {
+        let input = Solvable::parse(self, &normalize(input, S::INPUT_MODE))?;
+        Ok(Box::new(WithSolver {
+            solver: self,
+            input,
+        }))
+    }
+}
+
+impl<S: Solvable> DynParsed for WithSolver<'_, S> {
+    fn solve(&self, part: Part) -> Result<Solution, SolveError> {
+        match part {
+            Part::First => self.solver.first(&self.input),
+            Part::Second => self.solver.second(&self.input),
+        }
+    }
+}
+
+/// A day's input after parsing, ready to solve either part.
+pub struct Parsed<'a> {
+    day: usize,
+    input: Box<dyn DynParsed + 'a>,
+}
+
+impl Parsed<'_> {
+    pub fn solve(&self, part: Part) -> Result<Solution, SolveError> {
+        self.input
+            .solve(part)
+            .map_err(|e| e.in_part(self.day, part))
+    }
+}
+
+/// Solvers collected before the registry checks them, in registration order.
+#[derive(Default)]
+pub struct Registrations(Vec<Box<dyn DynSolvable>>);
+
+impl Registrations {
+    pub fn add<S: Solvable + 'static>(&mut self, solver: S) {
+        self.0.push(Box::new(solver));
+    }
+}
+
+/// A problem with the set of registered solvers.
+#[derive(Debug, PartialEq, Eq)]
+pub enum RegistryError {
+    Duplicate {
+        day: usize,
+        first: &'static str,
+        second: &'static str,
+    },
+    Missing {
+        day: usize,
+    },
+}
+
+impl Display for RegistryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Duplicate { day, first, second } => write!(
+                f,
+                "day {day} is registered twice, by '{first}' and by '{second}'"
+            ),
+            Self::Missing { day } => write!(f, "no solver is registered for day {day}"),
+        }
+    }
 }
 
 pub struct Registry {
-    solvers: Vec<Box<dyn Solvable>>,
+    solvers: BTreeMap<usize, Box<dyn DynSolvable>>,
 }
 
 impl Registry {
-    pub fn new() -> Self {
-        let mut solvers: Vec<Box<dyn Solvable>> = Vec::with_capacity(25);
-        solvers.push(Box::new(Day01));
-        solvers.push(Box::new(Day02));
-        solvers.push(Box::new(Day03));
-        solvers.push(Box::new(Day04));
-        solvers.push(Box::new(Day05));
-        solvers.push(Box::new(Day06));
-        solvers.push(Box::new(Day07));
-        solvers.push(Box::new(Day08));
-        solvers.push(Box::new(Day09));
-
-        Self { solvers }
-    }
-
-    pub fn solve(&self, inputs: &[String]) -> Vec<(Solution, Solution)> {
-        self.solvers
-            .iter()
-            .zip(inputs)
-            .map(|(solver, input)| (solver.first(input), solver.second(input)))
-            .collect()
+    /// The registry of every solver in `solutions`.
+    pub fn new() -> Result<Self, RegistryError> {
+        let mut registrations = Registrations::default();
+        solutions::register(&mut registrations);
+
+        Self::from_registrations(registrations)
+    }
+
+    /// Builds a registry, checking that every day from 1 up to the last one
+    /// has exactly one solver.
+    pub fn from_registrations(registrations: Registrations) -> Result<Self, RegistryError> {
+        let mut solvers: BTreeMap<usize, Box<dyn DynSolvable>> = BTreeMap::new();
+
+        for solver in registrations.0 {
+            let metadata = solver.metadata();
+            if let Some(previous) = solvers.get(&metadata.day) {
+                return Err(RegistryError::Duplicate {
+                    day: metadata.day,
+                    first: previous.metadata().title,
+                    second: metadata.title,
+                });
+            }
+            solvers.insert(metadata.day, solver);
+        }
+
+        if let Some(day) = (1..)
+            .zip(solvers.keys())
+            .find_map(|(d, &k)| (d != k).then_some(d))
+        {
+            return Err(RegistryError::Missing { day });
+        }
+
+        Ok(Self { solvers })
+    }
+
+    /// The days with a registered solver, in ascending order.
+    pub fn days(&self) -> impl Iterator<Item = usize> + '_ {
+        self.solvers.keys().copied()
+    }
+
+    pub fn contains(&self, day: usize) -> bool {
+        self.solvers.contains_key(&day)
+    }
+
+    pub fn metadata(&self, day: usize) -> Option<Metadata> {
+        self.solvers.get(&day).map(|solver| solver.metadata())
+    }
+
+    pub fn parse(&self, day: usize, input: &str) -> Option<Result<Parsed<'_>, SolveError>> {
+        let solver = self.solvers.get(&day)?;
+        Some(
+            solver
+                .parse(input)
+                .map(|input| Parsed { day, input })
+                .map_err(|e| e.in_day(day)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_big_numbers_equal_small_numbers() {
+        assert_eq!(Solution::new(42), Solution::BigNumber(BigInt::from(42)));
+        assert_eq!(Solution::new(42), Solution::from(BigUint::from(42u8)));
+        assert!(matches!(Solution::from(u128::MAX), Solution::BigNumber(_)));
+    }
+
+    #[test]
+    fn test_registry_is_keyed_by_metadata_day() {
+        let registry = Registry::new().unwrap();
+        for day in registry.days() {
+            assert_eq!(Some(day), registry.metadata(day).map(|m| m.day));
+        }
+    }
+
+    struct Fake<const DAY: usize>;
+
+    impl<const DAY: usize> RawSolvable for Fake<DAY> {
+        const METADATA: Metadata = Metadata {
+            year: 2025,
+            day: DAY,
+            title: "Fake",
+            tags: &[],
+        };
+
+        fn first(&self, _input: &str) -> Result<Solution, SolveError> {
+            Ok(Solution::new(DAY as i128))
+        }
+    }
+
+    #[test]
+    fn test_registry_rejects_duplicate_days() {
+        let mut registrations = Registrations::default();
+        registrations.add(Fake::<1>);
+        registrations.add(Fake::<1>);
+
+        assert_eq!(
+            Some(RegistryError::Duplicate {
+                day: 1,
+                first: "Fake",
+                second: "Fake"
+            }),
+            Registry::from_registrations(registrations).err()
+        );
+    }
+
+    #[test]
+    fn test_registry_rejects_gaps() {
+        let mut registrations = Registrations::default();
+        registrations.add(Fake::<3>);
+        registrations.add(Fake::<1>);
+
+        assert_eq!(
+            Some(RegistryError::Missing { day: 2 }),
+            Registry::from_registrations(registrations).err()
+        );
+    }
+
+    #[test]
+    fn test_metadata_matches_title_and_tags() {
+        let metadata = Metadata {
+            year: 2025,
+            day: 11,
+            title: "Reactor",
+            tags: &["graph"],
+        };
+
+        assert!(metadata.matches("react"));
+        assert!(metadata.matches("graph"));
+        assert!(!metadata.matches("grid"));
+    }
+
+    #[test]
+    fn test_solution_display() {
+        assert_eq!("The solution is 7", Solution::from(7u8).to_string());
+        assert_eq!("The solution is ABC", Solution::from("ABC").to_string());
+        assert_eq!(
+            "The solution is 1, 2",
+            Solution::from(vec![1, 2]).to_string()
+        );
+        assert_eq!("Not implemented yet", Solution::NotImplemented.to_string());
     }
 }
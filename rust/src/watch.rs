@@ -0,0 +1,155 @@
+use std::{
+    fs::metadata,
+    io,
+    path::Path,
+    thread,
+    time::{Instant, SystemTime},
+};
+
+use rust::{
+    Part, Registry, Solution, SolveError,
+    inputs::{InputError, read_input},
+};
+
+use crate::{cli::WatchOptions, runner::SEPARATOR};
+
+/// What identifies a version of the input file between two polls.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Stamp {
+    modified: Option<SystemTime>,
+    len: u64,
+}
+
+fn stamp(path: &Path) -> Option<Stamp> {
+    let metadata = metadata(path).ok()?;
+    Some(Stamp {
+        modified: metadata.modified().ok(),
+        len: metadata.len(),
+    })
+}
+
+/// How a result differs from the one of the previous run.
+fn change(
+    previous: Option<&Result<Solution, SolveError>>,
+    current: &Result<Solution, SolveError>,
+) -> String {
+    match previous {
+        None => String::new(),
+        Some(previous) if previous == current => " (unchanged)".to_string(),
+        Some(Ok(solution)) if solution.is_answer() => format!(" (was {})", solution.value()),
+        Some(Ok(solution)) => format!(" (was: {solution})"),
+        Some(Err(_)) => " (was an error)".to_string(),
+    }
+}
+
+/// Polls the input of the selected day and solves it again whenever the file
+/// changes, until the process is interrupted.
+pub fn watch(options: &WatchOptions) -> io::Result<()> {
+    let registry = Registry::new().map_err(|e| io::Error::other(e.to_string()))?;
+    let run = &options.run;
+    let day = run.days.as_ref().map_or(0, |d| *d.start());
+    let Some(metadata) = registry.metadata(day) else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("no solver registered for day {day}"),
+        ));
+    };
+    let locator = run.locator();
+    let parts: Vec<Part> = Part::ALL
+        .into_iter()
+        .filter(|&p| run.includes_part(p))
+        .collect();
+
+    println!(
+        "Watching day {day}: {} every {:?}, press Ctrl-C to stop",
+        metadata.title, options.interval
+    );
+
+    let mut last_stamp = None;
+    let mut waiting = false;
+    let mut previous: Vec<Option<Result<Solution, SolveError>>> = vec![None; parts.len()];
+
+    loop {
+        let path = match &run.input {
+            Some(path) => Ok(path.clone()),
+            None => locator.resolve(day),
+        };
+        let current = path.as_deref().ok().and_then(stamp);
+
+        match (path, current) {
+            (Ok(path), Some(current)) if last_stamp != Some(current) => {
+                last_stamp = Some(current);
+                waiting = false;
+                match read_input(&path) {
+                    Ok(input) => solve(&registry, day, &parts, &input, &mut previous),
+                    Err(e) => println!("Skipped: {e}"),
+                }
+                println!("{SEPARATOR}");
+            }
+            (Err(e), _) if !waiting => {
+                waiting = true;
+                println!("Waiting for input: {e}");
+            }
+            (Ok(path), None) if !waiting => {
+                waiting = true;
+                let e = InputError::Missing {
+                    day,
+                    tried: vec![path],
+                };
+                println!("Waiting for input: {e}");
+            }
+            _ => {}
+        }
+
+        thread::sleep(options.interval);
+    }
+}
+
+fn solve(
+    registry: &Registry,
+    day: usize,
+    parts: &[Part],
+    input: &str,
+    previous: &mut [Option<Result<Solution, SolveError>>],
+) {
+    let start = Instant::now();
+    let parsed = registry
+        .parse(day, input)
+        .expect("watched day has a registered solver");
+    println!("Parse: {:.2?}", start.elapsed());
+
+    for (&part, previous) in parts.iter().zip(previous) {
+        let start = Instant::now();
+        let result = match &parsed {
+            Ok(parsed) => parsed.solve(part),
+            Err(e) => Err(e.clone()),
+        };
+        let elapsed = start.elapsed();
+
+        let n = part.number();
+        let change = change(previous.as_ref(), &result);
+        match &result {
+            Ok(solution) => println!("Task {n} -> {solution} [{elapsed:.2?}]{change}"),
+            Err(e) => println!("Task {n} -> Error: {e} [{elapsed:.2?}]{change}"),
+        }
+        *previous = Some(result);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_change_from_previous_answer() {
+        let answer = Ok(Solution::new(42));
+
+        assert_eq!("", change(None, &answer));
+        assert_eq!(" (unchanged)", change(Some(&answer), &answer));
+        assert_eq!(" (was 41)", change(Some(&Ok(Solution::new(41))), &answer));
+        assert_eq!(
+            " (was an error)",
+            change(Some(&Err(SolveError::new("bad input"))), &answer)
+        );
+    }
+}